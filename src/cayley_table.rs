@@ -0,0 +1,246 @@
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use num::Real;
+use dimension::{Bitset, Dimension};
+use unit_basis_blade::UnitBasisBladeOf;
+use scaled_basis_blade::ScaledBasisBladeOf;
+use general_multivector::GeneralMultivector;
+use space::{Space, select_blade_product};
+use ops::{GradeSelection, outer_product_grade, left_contraction_grade, right_contraction_grade};
+
+/// The maximum dimension for which Cayley tables are generated. A table has `4^D` entries,
+/// products in spaces of a higher dimension are computed blade by blade.
+pub const MAX_CAYLEY_TABLE_DIMENSION: u8 = 10;
+
+/// The products for which Cayley tables are generated
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum ProductKind {
+    Geometric,
+    Outer,
+    LeftContraction,
+    RightContraction,
+}
+
+impl ProductKind {
+    /// The grade selection deriving the product from the geometric product,
+    /// `None` for the geometric product itself
+    pub fn selection(&self) -> Option<GradeSelection> {
+        match *self {
+            ProductKind::Geometric => None,
+            ProductKind::Outer => Some(outer_product_grade),
+            ProductKind::LeftContraction => Some(left_contraction_grade),
+            ProductKind::RightContraction => Some(right_contraction_grade),
+        }
+    }
+}
+
+/// A precomputed multiplication table of the basis blades of the orthogonal basis of a space.
+/// The product of the basis blades with the bitsets `lhs` and `rhs` is always the basis blade
+/// with the bitset `lhs ^ rhs` scaled by a factor, which is the sign of the reordering
+/// multiplied by the metric factor, or zero if the product vanishes.
+#[derive(Clone, PartialEq, Debug)]
+pub struct CayleyTable<R: Real> {
+    basis_blade_count: usize,
+    factors: Vec<R>,
+}
+
+impl<R: Real> CayleyTable<R> {
+    /// Generates the table of the product in the space `S`
+    pub fn new<S: Space<Scalar=R>>(kind: ProductKind) -> Self {
+        let basis_blade_count = 1 << S::Dimension::to_usize();
+        let selection = kind.selection();
+        let mut factors = Vec::with_capacity(basis_blade_count * basis_blade_count);
+
+        for lhs in 0..basis_blade_count {
+            let lhs_blade = ScaledBasisBladeOf::new(R::one(), UnitBasisBladeOf::new(Bitset::from_usize(lhs)));
+
+            for rhs in 0..basis_blade_count {
+                let rhs_blade = ScaledBasisBladeOf::new(R::one(), UnitBasisBladeOf::new(Bitset::from_usize(rhs)));
+                let product = S::geom_blades(&lhs_blade, &rhs_blade);
+
+                factors.push(select_blade_product(&lhs_blade, &rhs_blade, product, selection).scale());
+            }
+        }
+
+        CayleyTable {
+            basis_blade_count,
+            factors,
+        }
+    }
+
+    /// The number of rows and columns of the table, `2^D`
+    pub fn basis_blade_count(&self) -> usize {
+        self.basis_blade_count
+    }
+
+    /// The factor of the product of the basis blades with the bitsets `lhs` and `rhs`
+    #[inline]
+    pub fn factor(&self, lhs: usize, rhs: usize) -> R {
+        self.factors[lhs * self.basis_blade_count + rhs]
+    }
+
+    /// The bitset of the resulting basis blade and the factor of the product of the basis
+    /// blades with the bitsets `lhs` and `rhs`
+    #[inline]
+    pub fn entry(&self, lhs: usize, rhs: usize) -> (usize, R) {
+        (lhs ^ rhs, self.factor(lhs, rhs))
+    }
+
+    /// Computes the product of multivectors expressed in the orthogonal basis
+    pub fn product<D: Dimension>(&self, lhs: &GeneralMultivector<R, D>, rhs: &GeneralMultivector<R, D>)
+            -> GeneralMultivector<R, D> {
+        self.selected_product(lhs, rhs, |_, _, _| true)
+    }
+
+    /// Computes the product of multivectors expressed in the orthogonal basis, including only
+    /// the products of basis blades whose grades are accepted by `selection`
+    pub fn product_with_selection<D: Dimension>(&self, lhs: &GeneralMultivector<R, D>,
+                                                rhs: &GeneralMultivector<R, D>, selection: GradeSelection)
+            -> GeneralMultivector<R, D> {
+        self.selected_product(lhs, rhs, |lhs, rhs, result| {
+            selection(lhs.count_ones() as u8, rhs.count_ones() as u8) == Some(result.count_ones() as u8)
+        })
+    }
+
+    fn selected_product<D, F>(&self, lhs: &GeneralMultivector<R, D>, rhs: &GeneralMultivector<R, D>, selected: F)
+            -> GeneralMultivector<R, D>
+            where D: Dimension,
+                  F: Fn(usize, usize, usize) -> bool {
+        assert_eq!(lhs.coefficients().len(), self.basis_blade_count, "The dimensions of the table and the multivector differ.");
+
+        let mut coefficients = vec![R::zero(); self.basis_blade_count];
+
+        for (lhs_index, &lhs_coefficient) in lhs.coefficients().iter().enumerate() {
+            if lhs_coefficient.is_zero() {
+                continue;
+            }
+
+            let row = &self.factors[lhs_index * self.basis_blade_count..(lhs_index + 1) * self.basis_blade_count];
+
+            for (rhs_index, &rhs_coefficient) in rhs.coefficients().iter().enumerate() {
+                let factor = row[rhs_index];
+
+                if rhs_coefficient.is_zero() || factor.is_zero() {
+                    continue;
+                }
+
+                let result_index = lhs_index ^ rhs_index;
+
+                if selected(lhs_index, rhs_index, result_index) {
+                    coefficients[result_index] += lhs_coefficient * rhs_coefficient * factor;
+                }
+            }
+        }
+
+        GeneralMultivector::from_coefficients(coefficients)
+    }
+}
+
+type CayleyTables = HashMap<(TypeId, ProductKind), Box<dyn Any + Send + Sync>>;
+
+lazy_static! {
+    /// Tables generated by any thread, keyed by the type of the space and the product.
+    /// The values are `Arc<CayleyTable<S::Scalar>>`.
+    static ref CAYLEY_TABLES: Mutex<CayleyTables> = Mutex::new(HashMap::new());
+}
+
+/// Locks the cache. The map is never left in an inconsistent state, so a lock poisoned
+/// by a panicking thread is recovered instead of failing every later product.
+fn lock_cayley_tables() -> MutexGuard<'static, CayleyTables> {
+    CAYLEY_TABLES.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Returns the table of the product in the space `S`, generating it on the first use in the
+/// process. `None` if the dimension of the space exceeds `MAX_CAYLEY_TABLE_DIMENSION`.
+pub fn cayley_table<S: Space>(kind: ProductKind) -> Option<Arc<CayleyTable<S::Scalar>>> {
+    if S::Dimension::to_u8() > MAX_CAYLEY_TABLE_DIMENSION {
+        return None;
+    }
+
+    let key = (TypeId::of::<S>(), kind);
+
+    if let Some(table) = lock_cayley_tables().get(&key) {
+        return table.downcast_ref::<Arc<CayleyTable<S::Scalar>>>().cloned();
+    }
+
+    // The table is generated without holding the lock, so that products in other spaces are
+    // not blocked. If another thread inserts the same table meanwhile, its table is kept.
+    let table = Arc::new(CayleyTable::new::<S>(kind));
+
+    lock_cayley_tables().entry(key)
+        .or_insert_with(|| Box::new(table))
+        .downcast_ref::<Arc<CayleyTable<S::Scalar>>>()
+        .cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dimension::Const;
+    use typenum::{U0, U1, U3};
+    use space::{Euclidean, Signature};
+
+    type Projective = Signature<f64, U3, U0, U1>;
+
+    #[test]
+    fn table_entries() {
+        let geometric = CayleyTable::new::<Projective>(ProductKind::Geometric);
+        let outer = CayleyTable::new::<Projective>(ProductKind::Outer);
+        let left = CayleyTable::new::<Projective>(ProductKind::LeftContraction);
+
+        assert_eq!(geometric.basis_blade_count(), 16);
+        // e1 e2 = e12, e2 e1 = -e12, e0 e0 = 0
+        assert_eq!(geometric.entry(0b0001, 0b0010), (0b0011, 1.0));
+        assert_eq!(geometric.entry(0b0010, 0b0001), (0b0011, -1.0));
+        assert_eq!(geometric.factor(0b1000, 0b1000), 0.0);
+        assert_eq!(outer.factor(0b0001, 0b0001), 0.0);
+        assert_eq!(outer.factor(0b0010, 0b0001), -1.0);
+        // e1 << e12 = e2, e12 << e1 = 0
+        assert_eq!(left.entry(0b0001, 0b0011), (0b0010, 1.0));
+        assert_eq!(left.factor(0b0011, 0b0001), 0.0);
+    }
+
+    #[test]
+    fn table_products_match_blade_products() {
+        let lhs = GeneralMultivector::<f64, Const<4>>::from_coefficients((0..16).map(|i| i as f64 - 7.5).collect());
+        let rhs = GeneralMultivector::<f64, Const<4>>::from_coefficients((0..16).map(|i| (i * i % 7) as f64).collect());
+        let table = CayleyTable::new::<Projective>(ProductKind::Geometric);
+        let mut expected = GeneralMultivector::zero();
+
+        for lhs_blade in lhs.blades() {
+            for rhs_blade in rhs.blades() {
+                expected.add_blade(&Projective::geom_blades(&lhs_blade, &rhs_blade));
+            }
+        }
+
+        assert_eq!(table.product(&lhs, &rhs), expected);
+        assert_eq!(table.product_with_selection(&lhs, &rhs, left_contraction_grade),
+                   CayleyTable::new::<Projective>(ProductKind::LeftContraction).product(&lhs, &rhs));
+    }
+
+    #[test]
+    fn tables_are_cached() {
+        let first = cayley_table::<Euclidean<f64, Const<3>>>(ProductKind::Geometric).unwrap();
+        let second = cayley_table::<Euclidean<f64, Const<3>>>(ProductKind::Geometric).unwrap();
+        let outer = cayley_table::<Euclidean<f64, Const<3>>>(ProductKind::Outer).unwrap();
+
+        assert!(Arc::ptr_eq(&first, &second));
+        assert!(!Arc::ptr_eq(&first, &outer));
+        assert!(!Arc::ptr_eq(&first, &cayley_table::<Projective>(ProductKind::Geometric).unwrap()));
+
+        // The tables are shared between threads
+        let from_thread = ::std::thread::spawn(|| cayley_table::<Euclidean<f64, Const<3>>>(ProductKind::Geometric).unwrap())
+            .join()
+            .unwrap();
+        assert!(Arc::ptr_eq(&first, &from_thread));
+
+        // A thread panicking while holding the lock does not break the cache
+        let panicked = ::std::thread::spawn(|| {
+            let _tables = lock_cayley_tables();
+            panic!("Poisoning the lock of the cache.");
+        }).join();
+        assert!(panicked.is_err());
+        assert!(Arc::ptr_eq(&first, &cayley_table::<Euclidean<f64, Const<3>>>(ProductKind::Geometric).unwrap()));
+    }
+}
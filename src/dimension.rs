@@ -1,9 +1,9 @@
-use typenum::{Unsigned, IsLessOrEqual, True};
-use generic_array::ArrayLength;
-
-pub trait BitsetMask<T> {
-    fn bitset_mask() -> T;
-}
+use std::cmp::Ordering;
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::ops::{BitAnd, BitOr, BitXor, Not, Shl, Shr};
+use typenum::Unsigned;
+use typenum::consts::*;
 
 pub trait CountBits {
     fn count_bits(&self) -> u8;
@@ -13,59 +13,473 @@ pub trait Grade {
     fn grade(&self) -> u8;
 }
 
-macro_rules! define_max_dimension {
-    ($bitset_type:ty, $max_dimension:ident, $to_uint:ident) => {
-        use typenum::$max_dimension;
-        pub type DimensionBitset = $bitset_type;
-        #[allow(dead_code)]
-        pub type MaxDimension = $max_dimension;
+/// An unsigned integer used to store the set of basis vectors of a basis blade,
+/// where the bit `i` is set if the basis vector with index `i` is contained
+pub trait Bitset: Copy + Eq + Ord + Hash + Debug + Default + CountBits + 'static
+        + BitAnd<Output=Self> + BitOr<Output=Self> + BitXor<Output=Self> + Not<Output=Self>
+        + Shl<u8, Output=Self> + Shr<u8, Output=Self> {
+    /// The number of bits of the integer
+    const BITS: u16;
 
-        impl<T: Dimension> BitsetMask<DimensionBitset> for T {
-            #[inline]
-            fn bitset_mask() -> DimensionBitset {
-                ((1 as $bitset_type) << T::$to_uint()) - 1
-            }
+    fn zero() -> Self;
+
+    fn one() -> Self;
+
+    /// The bitset with the lowest `dimension` bits set, which does not overflow
+    /// if `dimension` is the number of bits of the integer
+    #[inline]
+    fn mask(dimension: u8) -> Self {
+        if dimension as u16 >= Self::BITS {
+            !Self::zero()
+        } else {
+            !(!Self::zero() << dimension)
+        }
+    }
+
+    /// The bitset containing only the basis vector with the given index
+    #[inline]
+    fn basis_vector(index: u8) -> Self {
+        Self::one() << index
+    }
+
+    /// Returns `true` if an odd number of swaps of basis vectors is required to bring
+    /// the concatenation of the basis blades `self` and `rhs` into canonical order
+    fn canonical_reordering_is_odd(self, rhs: Self) -> bool {
+        let mut lbs = self;
+        let mut total_swaps: u32 = 0;
+
+        while lbs > Self::one() {
+            lbs = lbs >> 1;
+            total_swaps += (lbs & rhs).count_bits() as u32;
         }
 
-        impl CountBits for DimensionBitset {
-            /// Counts the number of bits in a `DimensionBitset`
-            fn count_bits(&self) -> u8 {
-                let mut bitset = *self;
-                let mut count = 0u8;
+        total_swaps % 2 != 0
+    }
+
+    /// Converts the bitset to an index into the coefficients of a dense multivector.
+    /// Panics if the bitset contains bits which do not fit into `usize`.
+    fn to_usize(self) -> usize;
+
+    /// Converts an index into the coefficients of a dense multivector to a bitset,
+    /// truncating the bits which do not fit
+    fn from_usize(index: usize) -> Self;
+
+    /// Converts the bitset to an integer.
+    /// Panics if the bitset contains bits which do not fit into `u64`.
+    fn to_u64(self) -> u64;
+
+    /// Converts the integer to a bitset, truncating the bits which do not fit
+    fn from_u64(value: u64) -> Self;
+}
+
+macro_rules! impl_bitset {
+    ($($type:ident, $bits:expr);*) => {
+        $(
+            impl CountBits for $type {
+                /// Counts the number of bits set in the bitset
+                #[inline]
+                fn count_bits(&self) -> u8 {
+                    self.count_ones() as u8
+                }
+            }
+
+            impl Bitset for $type {
+                const BITS: u16 = $bits;
+
+                #[inline]
+                fn zero() -> Self {
+                    0
+                }
+
+                #[inline]
+                fn one() -> Self {
+                    1
+                }
+
+                #[inline]
+                fn to_usize(self) -> usize {
+                    let index = self as usize;
+                    assert!(index as $type == self, "The bitset {:#x} does not fit into `usize`.", self);
+                    index
+                }
 
-                while bitset != 0 {
-                    count += bitset & 1;
-                    bitset >>= 1;
+                #[inline]
+                fn from_usize(index: usize) -> Self {
+                    index as $type
                 }
 
-                count
+                #[inline]
+                fn to_u64(self) -> u64 {
+                    let value = self as u64;
+                    assert!(value as $type == self, "The bitset {:#x} does not fit into `u64`.", self);
+                    value
+                }
+
+                #[inline]
+                fn from_u64(value: u64) -> Self {
+                    value as $type
+                }
             }
+        )*
+    }
+}
+
+impl_bitset!(u8, 8; u16, 16; u32, 32; u64, 64; u128, 128);
+
+/// The words of a `WideBitset`, an array of `u64` with the least significant word first
+pub trait BitsetWords: Copy + Eq + Hash + Debug + Default + 'static {
+    const WORDS: usize;
+
+    fn words(&self) -> &[u64];
+
+    fn words_mut(&mut self) -> &mut [u64];
+}
+
+macro_rules! impl_bitset_words {
+    ($($words:expr),*) => {
+        $(
+            impl BitsetWords for [u64; $words] {
+                const WORDS: usize = $words;
+
+                #[inline]
+                fn words(&self) -> &[u64] {
+                    self
+                }
+
+                #[inline]
+                fn words_mut(&mut self) -> &mut [u64] {
+                    self
+                }
+            }
+        )*
+    }
+}
+
+impl_bitset_words!(3, 4);
+
+/// A bitset of multiple `u64` words, for dimensions exceeding the width of the primitive integers.
+/// Blades of such dimensions are only practical in a `SparseMultivector`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub struct WideBitset<W: BitsetWords>(pub W);
+
+impl<W: BitsetWords> WideBitset<W> {
+    #[inline]
+    fn zip_with<F: Fn(u64, u64) -> u64>(mut self, rhs: Self, f: F) -> Self {
+        for (word, rhs_word) in self.0.words_mut().iter_mut().zip(rhs.0.words()) {
+            *word = f(*word, *rhs_word);
+        }
+
+        self
+    }
+}
+
+impl<W: BitsetWords> BitAnd for WideBitset<W> {
+    type Output = Self;
+
+    #[inline]
+    fn bitand(self, rhs: Self) -> Self {
+        self.zip_with(rhs, |lhs, rhs| lhs & rhs)
+    }
+}
+
+impl<W: BitsetWords> BitOr for WideBitset<W> {
+    type Output = Self;
+
+    #[inline]
+    fn bitor(self, rhs: Self) -> Self {
+        self.zip_with(rhs, |lhs, rhs| lhs | rhs)
+    }
+}
+
+impl<W: BitsetWords> BitXor for WideBitset<W> {
+    type Output = Self;
+
+    #[inline]
+    fn bitxor(self, rhs: Self) -> Self {
+        self.zip_with(rhs, |lhs, rhs| lhs ^ rhs)
+    }
+}
+
+impl<W: BitsetWords> Not for WideBitset<W> {
+    type Output = Self;
+
+    #[inline]
+    fn not(mut self) -> Self {
+        for word in self.0.words_mut() {
+            *word = !*word;
+        }
+
+        self
+    }
+}
+
+impl<W: BitsetWords> Shl<u8> for WideBitset<W> {
+    type Output = Self;
+
+    fn shl(self, shift: u8) -> Self {
+        let word_shift = shift as usize / 64;
+        let bit_shift = shift as u32 % 64;
+        let words = self.0.words();
+        let mut result = Self::default();
+
+        for index in word_shift..W::WORDS {
+            let source = index - word_shift;
+            let carry = if bit_shift > 0 && source > 0 { words[source - 1] >> (64 - bit_shift) } else { 0 };
+
+            result.0.words_mut()[index] = (words[source] << bit_shift) | carry;
+        }
+
+        result
+    }
+}
+
+impl<W: BitsetWords> Shr<u8> for WideBitset<W> {
+    type Output = Self;
+
+    fn shr(self, shift: u8) -> Self {
+        let word_shift = shift as usize / 64;
+        let bit_shift = shift as u32 % 64;
+        let words = self.0.words();
+        let mut result = Self::default();
+
+        for index in 0..W::WORDS.saturating_sub(word_shift) {
+            let source = index + word_shift;
+            let carry = if bit_shift > 0 && source + 1 < W::WORDS { words[source + 1] << (64 - bit_shift) } else { 0 };
+
+            result.0.words_mut()[index] = (words[source] >> bit_shift) | carry;
         }
+
+        result
+    }
+}
+
+impl<W: BitsetWords> PartialOrd for WideBitset<W> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<W: BitsetWords> Ord for WideBitset<W> {
+    /// Compares the bitsets as unsigned integers, starting from the most significant word
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.words().iter().rev().cmp(other.0.words().iter().rev())
     }
 }
 
-#[cfg(feature = "max-dimension-8")]
-define_max_dimension!(u8, U8, to_u8);
-#[cfg(feature = "max-dimension-16")]
-define_max_dimension!(u16, U16, to_u16);
-#[cfg(feature = "max-dimension-32")]
-define_max_dimension!(u32, U32, to_u32);
-#[cfg(feature = "max-dimension-64")]
-define_max_dimension!(u64, U64, to_u64);
+impl<W: BitsetWords> CountBits for WideBitset<W> {
+    #[inline]
+    fn count_bits(&self) -> u8 {
+        self.0.words().iter().fold(0, |count, word| count + word.count_ones()) as u8
+    }
+}
+
+impl<W: BitsetWords> Bitset for WideBitset<W> {
+    const BITS: u16 = W::WORDS as u16 * 64;
+
+    #[inline]
+    fn zero() -> Self {
+        Self::default()
+    }
+
+    #[inline]
+    fn one() -> Self {
+        Self::from_u64(1)
+    }
+
+    #[inline]
+    fn to_usize(self) -> usize {
+        self.to_u64().to_usize()
+    }
 
-pub trait Dimension: Sized + Default + Unsigned + ArrayLength<bool> + IsLessOrEqual<MaxDimension, Output=True> {}
+    #[inline]
+    fn from_usize(index: usize) -> Self {
+        Self::from_u64(index as u64)
+    }
+
+    #[inline]
+    fn to_u64(self) -> u64 {
+        let words = self.0.words();
+        assert!(words[1..].iter().all(|&word| word == 0), "The bitset {:?} does not fit into `u64`.", words);
+        words[0]
+    }
 
-impl<T> Dimension for T where T: Sized + Default + Unsigned + ArrayLength<bool> + IsLessOrEqual<MaxDimension, Output=True> {}
+    #[inline]
+    fn from_u64(value: u64) -> Self {
+        let mut result = Self::default();
+        result.0.words_mut()[0] = value;
+        result
+    }
+}
+
+/// A dimension given as a const generic parameter, `Const<3>` is the dimension of the
+/// three-dimensional spaces
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Const<const D: usize>;
+
+/// The dimension of a space, implemented by `Const<D>` for every `D` up to 255.
+/// Every dimension selects the smallest `Bitset` able to store all of its basis vectors,
+/// so that blades of spaces of different dimensions may be used in a single program.
+/// Larger dimensions are rejected at compile time:
+///
+/// ```compile_fail
+/// use hestenes::UnitBasisBlade;
+///
+/// let blade = UnitBasisBlade::<256>::default();
+/// ```
+pub trait Dimension: Sized + Copy + Default + Debug + Eq + Ord + Hash + 'static {
+    type Bitset: Bitset;
+
+    /// The number of basis vectors of the space
+    const DIMENSION: u8;
+
+    #[inline]
+    fn to_u8() -> u8 {
+        Self::DIMENSION
+    }
+
+    #[inline]
+    fn to_usize() -> usize {
+        Self::DIMENSION as usize
+    }
+
+    /// The bitset containing all basis vectors of the space
+    #[inline]
+    fn bitset_mask() -> Self::Bitset {
+        Self::Bitset::mask(Self::DIMENSION)
+    }
+}
+
+/// The bitset type used by the blades of the dimension `D`
+pub type DimensionBitset<D> = <D as Dimension>::Bitset;
+
+/// Converts a `typenum` unsigned integer into the dimension of the same size. This keeps
+/// code that computes dimensions at the type level working, such as the dimension `P + Q + Z`
+/// of a `Signature`.
+pub trait IntoDimension: Unsigned {
+    type Dimension: Dimension;
+}
+
+/// The dimension of the `typenum` unsigned integer `N`, `Dim<U3>` is `Const<3>`
+pub type Dim<N> = <N as IntoDimension>::Dimension;
+
+macro_rules! impl_dimension {
+    ($bitset_type:ty; $($dimension:tt => $typenum:ident),*) => {
+        $(
+            impl Dimension for Const<$dimension> {
+                type Bitset = $bitset_type;
+
+                const DIMENSION: u8 = $dimension;
+            }
+
+            impl IntoDimension for $typenum {
+                type Dimension = Const<$dimension>;
+            }
+        )*
+    }
+}
+
+impl_dimension!(u8; 0 => U0, 1 => U1, 2 => U2, 3 => U3, 4 => U4, 5 => U5, 6 => U6, 7 => U7, 8 => U8);
+impl_dimension!(u16; 9 => U9, 10 => U10, 11 => U11, 12 => U12, 13 => U13, 14 => U14, 15 => U15, 16 => U16);
+impl_dimension!(u32; 17 => U17, 18 => U18, 19 => U19, 20 => U20, 21 => U21, 22 => U22, 23 => U23, 24 => U24,
+                     25 => U25, 26 => U26, 27 => U27, 28 => U28, 29 => U29, 30 => U30, 31 => U31, 32 => U32);
+impl_dimension!(u64; 33 => U33, 34 => U34, 35 => U35, 36 => U36, 37 => U37, 38 => U38, 39 => U39, 40 => U40,
+                     41 => U41, 42 => U42, 43 => U43, 44 => U44, 45 => U45, 46 => U46, 47 => U47, 48 => U48,
+                     49 => U49, 50 => U50, 51 => U51, 52 => U52, 53 => U53, 54 => U54, 55 => U55, 56 => U56,
+                     57 => U57, 58 => U58, 59 => U59, 60 => U60, 61 => U61, 62 => U62, 63 => U63, 64 => U64);
+impl_dimension!(u128; 65 => U65, 66 => U66, 67 => U67, 68 => U68, 69 => U69, 70 => U70, 71 => U71, 72 => U72,
+                      73 => U73, 74 => U74, 75 => U75, 76 => U76, 77 => U77, 78 => U78, 79 => U79, 80 => U80,
+                      81 => U81, 82 => U82, 83 => U83, 84 => U84, 85 => U85, 86 => U86, 87 => U87, 88 => U88,
+                      89 => U89, 90 => U90, 91 => U91, 92 => U92, 93 => U93, 94 => U94, 95 => U95, 96 => U96,
+                      97 => U97, 98 => U98, 99 => U99, 100 => U100, 101 => U101, 102 => U102, 103 => U103,
+                      104 => U104, 105 => U105, 106 => U106, 107 => U107, 108 => U108, 109 => U109,
+                      110 => U110, 111 => U111, 112 => U112, 113 => U113, 114 => U114, 115 => U115,
+                      116 => U116, 117 => U117, 118 => U118, 119 => U119, 120 => U120, 121 => U121,
+                      122 => U122, 123 => U123, 124 => U124, 125 => U125, 126 => U126, 127 => U127,
+                      128 => U128);
+impl_dimension!(WideBitset<[u64; 3]>; 129 => U129, 130 => U130, 131 => U131, 132 => U132, 133 => U133,
+                                      134 => U134, 135 => U135, 136 => U136, 137 => U137, 138 => U138,
+                                      139 => U139, 140 => U140, 141 => U141, 142 => U142, 143 => U143,
+                                      144 => U144, 145 => U145, 146 => U146, 147 => U147, 148 => U148,
+                                      149 => U149, 150 => U150, 151 => U151, 152 => U152, 153 => U153,
+                                      154 => U154, 155 => U155, 156 => U156, 157 => U157, 158 => U158,
+                                      159 => U159, 160 => U160, 161 => U161, 162 => U162, 163 => U163,
+                                      164 => U164, 165 => U165, 166 => U166, 167 => U167, 168 => U168,
+                                      169 => U169, 170 => U170, 171 => U171, 172 => U172, 173 => U173,
+                                      174 => U174, 175 => U175, 176 => U176, 177 => U177, 178 => U178,
+                                      179 => U179, 180 => U180, 181 => U181, 182 => U182, 183 => U183,
+                                      184 => U184, 185 => U185, 186 => U186, 187 => U187, 188 => U188,
+                                      189 => U189, 190 => U190, 191 => U191, 192 => U192);
+impl_dimension!(WideBitset<[u64; 4]>; 193 => U193, 194 => U194, 195 => U195, 196 => U196, 197 => U197,
+                                      198 => U198, 199 => U199, 200 => U200, 201 => U201, 202 => U202,
+                                      203 => U203, 204 => U204, 205 => U205, 206 => U206, 207 => U207,
+                                      208 => U208, 209 => U209, 210 => U210, 211 => U211, 212 => U212,
+                                      213 => U213, 214 => U214, 215 => U215, 216 => U216, 217 => U217,
+                                      218 => U218, 219 => U219, 220 => U220, 221 => U221, 222 => U222,
+                                      223 => U223, 224 => U224, 225 => U225, 226 => U226, 227 => U227,
+                                      228 => U228, 229 => U229, 230 => U230, 231 => U231, 232 => U232,
+                                      233 => U233, 234 => U234, 235 => U235, 236 => U236, 237 => U237,
+                                      238 => U238, 239 => U239, 240 => U240, 241 => U241, 242 => U242,
+                                      243 => U243, 244 => U244, 245 => U245, 246 => U246, 247 => U247,
+                                      248 => U248, 249 => U249, 250 => U250, 251 => U251, 252 => U252,
+                                      253 => U253, 254 => U254, 255 => U255);
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use typenum::U6;
 
     #[test]
     fn unit_basis_blade_from_u8() {
-        let mask = U6::bitset_mask();
+        let mask = Const::<6>::bitset_mask();
 
         assert_eq!(mask, 63);
     }
+
+    #[test]
+    fn bitset_mask_full_width() {
+        assert_eq!(Const::<0>::bitset_mask(), 0);
+        assert_eq!(Const::<8>::bitset_mask(), 0xFF);
+        assert_eq!(Const::<12>::bitset_mask(), 0x0FFF);
+        assert_eq!(Const::<32>::bitset_mask(), 0xFFFF_FFFF);
+        assert_eq!(Const::<40>::bitset_mask(), 0xFF_FFFF_FFFF);
+        assert_eq!(Const::<64>::bitset_mask(), !0u64);
+    }
+
+    #[test]
+    fn typenum_dimensions() {
+        assert_eq!(Dim::<U3>::default(), Const::<3>);
+        assert_eq!(Dim::<U200>::to_u8(), 200);
+        assert_eq!(<Dim<U64> as Dimension>::bitset_mask(), !0u64);
+    }
+
+    #[test]
+    fn wide_bitsets() {
+        assert_eq!(Const::<128>::bitset_mask(), !0u128);
+        assert_eq!(Const::<100>::bitset_mask().count_bits(), 100);
+        assert_eq!(Const::<192>::bitset_mask(), WideBitset([!0, !0, !0]));
+        assert_eq!(Const::<200>::bitset_mask(), WideBitset([!0, !0, !0, 0xFF]));
+        assert_eq!(Const::<255>::bitset_mask().count_bits(), 255);
+
+        let low = WideBitset::<[u64; 4]>::basis_vector(63);
+        let high = WideBitset::<[u64; 4]>::basis_vector(199);
+
+        assert_eq!(low << 1, WideBitset([0, 1, 0, 0]));
+        assert_eq!(high >> 136, low);
+        assert_eq!(high << 100, WideBitset::zero());
+        assert!(low < high && high < (high | low));
+        assert_eq!((!high & Const::<200>::bitset_mask()).count_bits(), 199);
+        assert_eq!(low.to_u64(), 1 << 63);
+    }
+
+    #[test]
+    #[should_panic]
+    fn wide_bitset_to_u64_rejects_high_bits() {
+        WideBitset::<[u64; 3]>::basis_vector(64).to_u64();
+    }
+
+    #[test]
+    #[should_panic]
+    fn u128_to_usize_rejects_high_bits() {
+        (1u128 << 100).to_usize();
+    }
 }
@@ -0,0 +1,495 @@
+//! Spaces, blades and multivectors whose dimension and signature are chosen at runtime.
+//!
+//! Products are computed with the same kernels as those of the statically typed spaces.
+//! Operations on operands of different spaces fail with a `DynError`.
+
+use std::error::Error;
+use std::fmt;
+use std::ops::{Mul, Neg};
+use std::slice;
+use num::Real;
+use dimension::{Bitset, Const, CountBits, Dimension, Grade};
+use unit_basis_blade::{UnitBasisBlade, UnitBasisBladeOf};
+use scaled_basis_blade::{ScaledBasisBlade, ScaledBasisBladeOf};
+use general_multivector::GeneralMultivector;
+use sparse_multivector::SparseMultivector;
+use space::{geom_blades_with, metric_factor, select_blade_product, signature_basis_vector_square};
+use cayley_table::ProductKind;
+use ops::{GradeSelection, Reverse};
+
+/// The maximum dimension of a `DynSpace`, the number of bits of the bitset of a `DynBlade`
+pub const MAX_DYN_DIMENSION: u8 = 64;
+
+/// An error of an operation on dynamic spaces, blades or multivectors
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DynError {
+    /// The dimension `p + q + r` exceeds `MAX_DYN_DIMENSION`
+    DimensionTooLarge(usize),
+    /// The bitset contains basis vectors which are not part of the space
+    InvalidBitset { space: DynSpace, bitset: u64 },
+    /// The operands belong to different spaces
+    SpaceMismatch { lhs: DynSpace, rhs: DynSpace },
+    /// The dimension of a statically typed blade or multivector differs from the dimension of the space
+    DimensionMismatch { space: DynSpace, dimension: u8 },
+}
+
+impl fmt::Display for DynError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            DynError::DimensionTooLarge(dimension) => {
+                write!(f, "the dimension {} exceeds the maximum dimension {}", dimension, MAX_DYN_DIMENSION)
+            },
+            DynError::InvalidBitset { space, bitset } => {
+                write!(f, "the bitset {:#b} contains basis vectors which are not part of {}", bitset, space)
+            },
+            DynError::SpaceMismatch { lhs, rhs } => {
+                write!(f, "the operands belong to different spaces, {} and {}", lhs, rhs)
+            },
+            DynError::DimensionMismatch { space, dimension } => {
+                write!(f, "the dimension {} differs from the dimension of {}", dimension, space)
+            },
+        }
+    }
+}
+
+impl Error for DynError {
+    fn description(&self) -> &str {
+        match *self {
+            DynError::DimensionTooLarge(_) => "dimension too large",
+            DynError::InvalidBitset { .. } => "invalid bitset",
+            DynError::SpaceMismatch { .. } => "space mismatch",
+            DynError::DimensionMismatch { .. } => "dimension mismatch",
+        }
+    }
+}
+
+/// A space with the signature `(p, q, r)` chosen at runtime, the runtime counterpart of `Signature`.
+/// The first `p` basis vectors square to 1, the next `q` to -1 and the last `r` to 0.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct DynSpace {
+    p: u8,
+    q: u8,
+    r: u8,
+}
+
+impl DynSpace {
+    pub fn new(p: u8, q: u8, r: u8) -> Result<Self, DynError> {
+        let dimension = p as usize + q as usize + r as usize;
+
+        if dimension > MAX_DYN_DIMENSION as usize {
+            return Err(DynError::DimensionTooLarge(dimension));
+        }
+
+        Ok(DynSpace { p, q, r })
+    }
+
+    pub fn euclidean(dimension: u8) -> Result<Self, DynError> {
+        Self::new(dimension, 0, 0)
+    }
+
+    pub fn p(&self) -> u8 {
+        self.p
+    }
+
+    pub fn q(&self) -> u8 {
+        self.q
+    }
+
+    pub fn r(&self) -> u8 {
+        self.r
+    }
+
+    pub fn dimension(&self) -> u8 {
+        self.p + self.q + self.r
+    }
+
+    /// The bitset containing all basis vectors of the space
+    pub fn bitset_mask(&self) -> u64 {
+        u64::mask(self.dimension())
+    }
+
+    /// The square `e_i * e_i` of the basis vector with index `i`, indices start from 0
+    pub fn basis_vector_square<R: Real>(&self, index: u8) -> R {
+        signature_basis_vector_square(self.p, self.q, index)
+    }
+
+    /// Computes the geometric product of two basis blades, which is the returned blade
+    /// scaled by the returned factor
+    pub fn geom_blades<R: Real>(&self, lhs: &DynBlade, rhs: &DynBlade) -> Result<(R, DynBlade), DynError> {
+        self.ensure_same(&lhs.space)?;
+        self.ensure_same(&rhs.space)?;
+
+        let product = self.geom_blade_product(&ScaledBasisBladeOf::new(R::one(), UnitBasisBladeOf::new(lhs.bitset)),
+                                              &ScaledBasisBladeOf::new(R::one(), UnitBasisBladeOf::new(rhs.bitset)));
+
+        Ok((product.scale(), DynBlade { space: *self, bitset: lhs.bitset ^ rhs.bitset }))
+    }
+
+    fn geom_blade_product<R: Real>(&self, lhs: &ScaledBasisBlade<R, 64>, rhs: &ScaledBasisBlade<R, 64>)
+            -> ScaledBasisBlade<R, 64> {
+        geom_blades_with(lhs, rhs, |blade| metric_factor(blade.bitset(), |index| self.basis_vector_square(index)))
+    }
+
+    fn ensure_same(&self, other: &DynSpace) -> Result<(), DynError> {
+        if self == other {
+            Ok(())
+        } else {
+            Err(DynError::SpaceMismatch { lhs: *self, rhs: *other })
+        }
+    }
+
+    fn ensure_dimension<D: Dimension>(&self) -> Result<(), DynError> {
+        if D::to_u8() == self.dimension() {
+            Ok(())
+        } else {
+            Err(DynError::DimensionMismatch { space: *self, dimension: D::to_u8() })
+        }
+    }
+}
+
+impl fmt::Display for DynSpace {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "R({}, {}, {})", self.p, self.q, self.r)
+    }
+}
+
+/// A unit basis blade of a `DynSpace`, the runtime counterpart of `UnitBasisBlade`
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct DynBlade {
+    space: DynSpace,
+    bitset: u64,
+}
+
+impl DynBlade {
+    /// The blade with the given bitset, an error if it contains basis vectors not part of the space
+    pub fn new(space: DynSpace, bitset: u64) -> Result<Self, DynError> {
+        if bitset & !space.bitset_mask() != 0 {
+            return Err(DynError::InvalidBitset { space, bitset });
+        }
+
+        Ok(DynBlade { space, bitset })
+    }
+
+    /// The blade of grade 0
+    pub fn scalar(space: DynSpace) -> Self {
+        DynBlade { space, bitset: 0 }
+    }
+
+    /// The basis vector with the given index, indices start from 0
+    pub fn basis_vector(space: DynSpace, index: u8) -> Result<Self, DynError> {
+        if index >= space.dimension() {
+            return Err(DynError::InvalidBitset { space, bitset: if index < 64 { 1 << index } else { 0 } });
+        }
+
+        Ok(DynBlade { space, bitset: 1 << index })
+    }
+
+    /// The unit pseudoscalar `I`, containing all basis vectors
+    pub fn pseudoscalar(space: DynSpace) -> Self {
+        DynBlade { space, bitset: space.bitset_mask() }
+    }
+
+    /// Converts a statically typed blade, an error if the dimensions differ
+    pub fn from_unit_basis_blade<D: Dimension>(space: DynSpace, blade: &UnitBasisBladeOf<D>) -> Result<Self, DynError> {
+        space.ensure_dimension::<D>()?;
+
+        Ok(DynBlade { space, bitset: blade.bitset().to_u64() })
+    }
+
+    /// Converts to a statically typed blade, an error if the dimensions differ
+    pub fn to_unit_basis_blade<D: Dimension>(&self) -> Result<UnitBasisBladeOf<D>, DynError> {
+        self.space.ensure_dimension::<D>()?;
+
+        Ok(UnitBasisBladeOf::new(Bitset::from_u64(self.bitset)))
+    }
+
+    pub fn space(&self) -> DynSpace {
+        self.space
+    }
+
+    pub fn bitset(&self) -> u64 {
+        self.bitset
+    }
+}
+
+impl Grade for DynBlade {
+    fn grade(&self) -> u8 {
+        self.bitset.count_bits()
+    }
+}
+
+/// A multivector of a `DynSpace`, the runtime counterpart of `SparseMultivector`,
+/// storing only the non-zero blades, sorted by bitset
+#[derive(Clone, PartialEq, Debug)]
+pub struct DynMultivector<R: Real> {
+    space: DynSpace,
+    multivector: SparseMultivector<R, Const<64>>,
+}
+
+impl<R: Real> DynMultivector<R> {
+    pub fn zero(space: DynSpace) -> Self {
+        DynMultivector {
+            space,
+            multivector: SparseMultivector::zero(),
+        }
+    }
+
+    pub fn scalar(space: DynSpace, scale: R) -> Self {
+        Self::from_blade(DynBlade::scalar(space), scale)
+    }
+
+    pub fn from_blade(blade: DynBlade, scale: R) -> Self {
+        DynMultivector {
+            space: blade.space,
+            multivector: ScaledBasisBladeOf::new(scale, UnitBasisBladeOf::new(blade.bitset)).into(),
+        }
+    }
+
+    /// Converts a statically typed multivector, an error if the dimensions differ
+    pub fn from_general_multivector<D: Dimension>(space: DynSpace, multivector: &GeneralMultivector<R, D>)
+            -> Result<Self, DynError> {
+        space.ensure_dimension::<D>()?;
+
+        Ok(DynMultivector {
+            space,
+            multivector: SparseMultivector::from_blades(multivector.blades().map(|blade| {
+                ScaledBasisBladeOf::new(blade.scale(), UnitBasisBladeOf::new(blade.unit_basis_blade().bitset().to_u64()))
+            })),
+        })
+    }
+
+    /// Converts to a statically typed multivector, an error if the dimensions differ
+    pub fn to_general_multivector<D: Dimension>(&self) -> Result<GeneralMultivector<R, D>, DynError> {
+        self.space.ensure_dimension::<D>()?;
+
+        let mut result = GeneralMultivector::zero();
+
+        for blade in self.multivector.blades() {
+            result.set_coefficient(&UnitBasisBladeOf::new(Bitset::from_u64(blade.unit_basis_blade().bitset())), blade.scale());
+        }
+
+        Ok(result)
+    }
+
+    pub fn space(&self) -> DynSpace {
+        self.space
+    }
+
+    pub fn coefficient(&self, blade: &DynBlade) -> Result<R, DynError> {
+        self.space.ensure_same(&blade.space)?;
+
+        Ok(self.multivector.coefficient(&UnitBasisBladeOf::new(blade.bitset)))
+    }
+
+    pub fn set_coefficient(&mut self, blade: &DynBlade, scale: R) -> Result<(), DynError> {
+        self.space.ensure_same(&blade.space)?;
+        self.multivector.set_coefficient(&UnitBasisBladeOf::new(blade.bitset), scale);
+
+        Ok(())
+    }
+
+    /// Iterates over all blades with a non-zero coefficient, ordered by bitset
+    pub fn blades(&self) -> DynBlades<R> {
+        DynBlades {
+            space: self.space,
+            terms: self.multivector.terms().iter(),
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.multivector.is_zero()
+    }
+
+    /// The grade projection `<A>_k`, keeping only the blades of grade `k`
+    pub fn grade(&self, k: u8) -> Self {
+        self.with_multivector(self.multivector.grade(k))
+    }
+
+    /// The squared norm `<A ~A>_0` under the metric of the space, which may be negative
+    /// or zero for non-zero multivectors in mixed or degenerate signatures
+    pub fn norm_squared(&self) -> R {
+        self.multivector.terms().iter().fold(R::zero(), |sum, &(ref blade, scale)| {
+            sum + scale * scale * metric_factor(blade.bitset(), |index| self.space.basis_vector_square(index))
+        })
+    }
+
+    /// The reverse `~A`
+    pub fn reverse(&self) -> Self {
+        self.with_multivector(self.multivector.clone().reverse())
+    }
+
+    pub fn add(&self, rhs: &Self) -> Result<Self, DynError> {
+        self.space.ensure_same(&rhs.space)?;
+
+        Ok(self.with_multivector(&self.multivector + &rhs.multivector))
+    }
+
+    pub fn sub(&self, rhs: &Self) -> Result<Self, DynError> {
+        self.space.ensure_same(&rhs.space)?;
+
+        Ok(self.with_multivector(&self.multivector - &rhs.multivector))
+    }
+
+    /// Computes the product of the given kind under the metric of the space
+    pub fn product_of_kind(&self, rhs: &Self, kind: ProductKind) -> Result<Self, DynError> {
+        self.selected_product(rhs, kind.selection())
+    }
+
+    /// Computes a product derived from the geometric product by grade selection
+    /// under the metric of the space
+    pub fn product(&self, rhs: &Self, selection: GradeSelection) -> Result<Self, DynError> {
+        self.selected_product(rhs, Some(selection))
+    }
+
+    pub fn geom(&self, rhs: &Self) -> Result<Self, DynError> {
+        self.product_of_kind(rhs, ProductKind::Geometric)
+    }
+
+    pub fn outer(&self, rhs: &Self) -> Result<Self, DynError> {
+        self.product_of_kind(rhs, ProductKind::Outer)
+    }
+
+    pub fn left_contraction(&self, rhs: &Self) -> Result<Self, DynError> {
+        self.product_of_kind(rhs, ProductKind::LeftContraction)
+    }
+
+    pub fn right_contraction(&self, rhs: &Self) -> Result<Self, DynError> {
+        self.product_of_kind(rhs, ProductKind::RightContraction)
+    }
+
+    fn selected_product(&self, rhs: &Self, selection: Option<GradeSelection>) -> Result<Self, DynError> {
+        self.space.ensure_same(&rhs.space)?;
+
+        let product = self.multivector.product_with(&rhs.multivector, |lhs, rhs| {
+            select_blade_product(lhs, rhs, self.space.geom_blade_product(lhs, rhs), selection)
+        });
+
+        Ok(self.with_multivector(product))
+    }
+
+    fn with_multivector(&self, multivector: SparseMultivector<R, Const<64>>) -> Self {
+        DynMultivector {
+            space: self.space,
+            multivector,
+        }
+    }
+}
+
+impl<R: Real> From<DynBlade> for DynMultivector<R> {
+    fn from(blade: DynBlade) -> Self {
+        Self::from_blade(blade, R::one())
+    }
+}
+
+/// An iterator over the non-zero blades of a `DynMultivector`
+pub struct DynBlades<'a, R: Real + 'a> {
+    space: DynSpace,
+    terms: slice::Iter<'a, (UnitBasisBlade<64>, R)>,
+}
+
+impl<'a, R: Real> Iterator for DynBlades<'a, R> {
+    type Item = (DynBlade, R);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let space = self.space;
+
+        self.terms.next().map(|&(ref blade, scale)| (DynBlade { space, bitset: blade.bitset() }, scale))
+    }
+}
+
+impl_operator! {
+    operator_type: [Mul];
+    inline: [false];
+    operator_fn: mul;
+    generics: [R: Real];
+    header: (DynMultivector<R>, R) -> DynMultivector<R>;
+    |&lhs, &rhs| {
+        lhs.with_multivector(&lhs.multivector * *rhs)
+    }
+}
+
+impl_unary_operator! {
+    operator_type: [Neg];
+    inline: [false];
+    operator_fn: neg;
+    generics: [R: Real];
+    header: (DynMultivector<R>) -> DynMultivector<R>;
+    |&this| {
+        this.with_multivector(-&this.multivector)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use typenum::{U0, U1, U2, U3};
+    use space::{Space, Signature};
+    use ops::Reverse;
+
+    type Projective = Signature<f64, U3, U0, U1>;
+
+    #[test]
+    fn dyn_blade_conversions() {
+        let space = DynSpace::new(3, 0, 1).unwrap();
+        let blade = DynBlade::new(space, 0b1010).unwrap();
+
+        assert_eq!(space.dimension(), 4);
+        assert_eq!(blade.grade(), 2);
+        assert_eq!(blade.to_unit_basis_blade::<Const<4>>().unwrap(), UnitBasisBladeOf::from(0b1010u8));
+        assert_eq!(DynBlade::from_unit_basis_blade(space, &UnitBasisBlade::<4>::from(0b1010u8)), Ok(blade));
+        assert_eq!(blade.to_unit_basis_blade::<Const<3>>(), Err(DynError::DimensionMismatch { space, dimension: 3 }));
+        assert_eq!(DynBlade::new(space, 0b10000), Err(DynError::InvalidBitset { space, bitset: 0b10000 }));
+        assert_eq!(DynSpace::new(40, 20, 10), Err(DynError::DimensionTooLarge(70)));
+        assert_eq!(DynBlade::pseudoscalar(DynSpace::euclidean(64).unwrap()).bitset(), !0);
+    }
+
+    #[test]
+    fn dyn_products_match_static_products() {
+        let space = DynSpace::new(3, 0, 1).unwrap();
+        let lhs = GeneralMultivector::<f64, Const<4>>::from_coefficients((0..16).map(|i| i as f64 - 7.0).collect());
+        let rhs = GeneralMultivector::<f64, Const<4>>::from_coefficients((0..16).map(|i| (i * i % 7) as f64).collect());
+        let dyn_lhs = DynMultivector::from_general_multivector(space, &lhs).unwrap();
+        let dyn_rhs = DynMultivector::from_general_multivector(space, &rhs).unwrap();
+
+        assert_eq!(dyn_lhs.geom(&dyn_rhs).unwrap().to_general_multivector::<Const<4>>().unwrap(),
+                   Projective::geom(&lhs, &rhs));
+        assert_eq!(dyn_lhs.outer(&dyn_rhs).unwrap().to_general_multivector::<Const<4>>().unwrap(),
+                   Projective::outer(&lhs, &rhs));
+        assert_eq!(dyn_lhs.left_contraction(&dyn_rhs).unwrap().to_general_multivector::<Const<4>>().unwrap(),
+                   Projective::left_contraction(&lhs, &rhs));
+        assert_eq!(dyn_lhs.reverse().to_general_multivector::<Const<4>>().unwrap(), lhs.clone().reverse());
+        assert!(dyn_lhs.sub(&dyn_lhs).unwrap().is_zero());
+
+        // e3 squares to 0, e0 to 1
+        let e3 = DynMultivector::<f64>::from(DynBlade::basis_vector(space, 3).unwrap());
+        assert!(e3.geom(&e3).unwrap().is_zero());
+        assert_eq!(space.geom_blades::<f64>(&DynBlade::new(space, 0b01).unwrap(), &DynBlade::new(space, 0b11).unwrap()),
+                   Ok((1.0, DynBlade::new(space, 0b10).unwrap())));
+    }
+
+    #[test]
+    fn dyn_norm_matches_static_norm() {
+        let space = DynSpace::new(1, 2, 0).unwrap();
+        let multivector = GeneralMultivector::<f64, Const<3>>::from_coefficients((0..8).map(|i| i as f64 - 2.5).collect());
+        let dyn_multivector = DynMultivector::from_general_multivector(space, &multivector).unwrap();
+        let e1 = DynMultivector::<f64>::from(DynBlade::basis_vector(space, 1).unwrap());
+
+        assert_eq!(dyn_multivector.norm_squared(), Signature::<f64, U1, U2, U0>::norm_squared(&multivector));
+        assert_eq!(e1.norm_squared(), -1.0);
+    }
+
+    #[test]
+    fn dyn_space_mismatch() {
+        let euclidean = DynSpace::euclidean(3).unwrap();
+        let minkowski = DynSpace::new(1, 2, 0).unwrap();
+        let a = DynMultivector::<f64>::from(DynBlade::basis_vector(euclidean, 1).unwrap());
+        let b = DynMultivector::<f64>::from(DynBlade::basis_vector(minkowski, 1).unwrap());
+        let error = DynError::SpaceMismatch { lhs: euclidean, rhs: minkowski };
+
+        assert_eq!(a.geom(&b), Err(error));
+        assert_eq!(a.add(&b), Err(error));
+        assert_eq!(a.coefficient(&DynBlade::scalar(minkowski)), Err(error));
+        assert_eq!(b.geom(&b).unwrap(), DynMultivector::scalar(minkowski, -1.0));
+        assert_eq!(error.to_string(), "the operands belong to different spaces, R(3, 0, 0) and R(1, 2, 0)");
+    }
+}
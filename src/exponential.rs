@@ -0,0 +1,182 @@
+use num::Real;
+use dimension::Dimension;
+use general_multivector::GeneralMultivector;
+use space::Space;
+
+/// The maximum number of terms of the exponential series to evaluate
+const MAX_SERIES_TERMS: usize = 100;
+
+/// The maximum number of squarings performed by the scaling-and-squaring method
+const MAX_SQUARINGS: i32 = 1100;
+
+/// The result of a computation which is possibly approximate
+#[derive(Clone, PartialEq, Debug)]
+pub struct Approximation<R: Real, D: Dimension> {
+    value: GeneralMultivector<R, D>,
+    error_bound: R,
+    converged: bool,
+}
+
+impl<R: Real, D: Dimension> Approximation<R, D> {
+    /// A result computed using a closed-form formula, which is only subject to rounding errors
+    pub fn exact(value: GeneralMultivector<R, D>) -> Self {
+        Approximation {
+            value,
+            error_bound: R::zero(),
+            converged: true,
+        }
+    }
+
+    pub fn value(&self) -> &GeneralMultivector<R, D> {
+        &self.value
+    }
+
+    pub fn into_value(self) -> GeneralMultivector<R, D> {
+        self.value
+    }
+
+    /// An estimate of the upper bound on the (Euclidean coefficient) norm of the error,
+    /// excluding rounding errors
+    pub fn error_bound(&self) -> R {
+        self.error_bound
+    }
+
+    /// `false` if the iteration limit was reached before the desired accuracy
+    pub fn converged(&self) -> bool {
+        self.converged
+    }
+}
+
+/// Computes the exponential of a bivector under the metric of the space `S`.
+///
+/// If the square of the bivector is a scalar, the closed-form formula is chosen
+/// by its sign:
+///
+/// * circular: `B^2 = -a^2 < 0`, `exp(B) = cos(a) + B sin(a)/a`,
+/// * hyperbolic: `B^2 = a^2 > 0`, `exp(B) = cosh(a) + B sinh(a)/a`,
+/// * parabolic: `B^2 = 0`, `exp(B) = 1 + B`.
+///
+/// Otherwise, for example for non-simple bivectors, the exponential series is evaluated
+/// using `exp`.
+pub fn exp_bivector<S, R, D>(bivector: &GeneralMultivector<R, D>) -> Approximation<R, D>
+        where S: Space<Scalar=R, Dimension=D>, R: Real, D: Dimension {
+    let square = S::geom(bivector, bivector);
+
+    if square.grades() & !1 != 0 {
+        return exp::<S, R, D>(bivector);
+    }
+
+    let square = square.coefficients()[0];
+    let one = GeneralMultivector::scalar(R::one());
+
+    Approximation::exact(if square < R::zero() {
+        let angle = (-square).sqrt();
+
+        one * angle.cos() + bivector * (angle.sin() / angle)
+    } else if square > R::zero() {
+        let angle = square.sqrt();
+
+        one * angle.cosh() + bivector * (angle.sinh() / angle)
+    } else {
+        one + bivector
+    })
+}
+
+/// Computes the exponential of an arbitrary multivector under the metric of the space `S`,
+/// by evaluating the exponential series of the multivector scaled down by `2^s`,
+/// and squaring the result `s` times.
+pub fn exp<S, R, D>(multivector: &GeneralMultivector<R, D>) -> Approximation<R, D>
+        where S: Space<Scalar=R, Dimension=D>, R: Real, D: Dimension {
+    let one = R::one();
+    let two = one + one;
+    let half = one / two;
+    let mut squarings = 0;
+    let mut scaled_norm = multivector.norm();
+
+    while scaled_norm > half && squarings < MAX_SQUARINGS {
+        scaled_norm = scaled_norm * half;
+        squarings += 1;
+    }
+
+    let scale = two.powi(squarings);
+    let scaled = multivector * (one / scale);
+    let tolerance = R::default_epsilon();
+    let mut sum = GeneralMultivector::scalar(one);
+    let mut term = GeneralMultivector::scalar(one);
+    let mut truncation = R::zero();
+    let mut converged = false;
+
+    for k in 1..MAX_SERIES_TERMS {
+        term = S::geom(&term, &scaled) * (one / R::from_usize(k).unwrap());
+        sum = sum + &term;
+        truncation = term.norm();
+
+        if truncation <= tolerance * sum.norm() {
+            converged = true;
+            break;
+        }
+    }
+
+    // The relative error of the series is amplified by every squaring
+    let relative_error = truncation / sum.norm();
+
+    for _ in 0..squarings {
+        sum = S::geom(&sum, &sum);
+    }
+
+    Approximation {
+        error_bound: relative_error * scale * sum.norm(),
+        value: sum,
+        converged,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dimension::Const;
+    use typenum::{U0, U1, U3};
+    use space::{Euclidean, Signature};
+    use unit_basis_blade::UnitBasisBladeOf;
+    use scaled_basis_blade::ScaledBasisBladeOf;
+    use test_util::assert_approx_eq;
+
+    type Minkowski = Signature<f64, U1, U3, U0>;
+    type Projective = Signature<f64, U3, U0, U1>;
+
+    fn blade<D: Dimension>(scale: f64, bitset: u8) -> GeneralMultivector<f64, D> {
+        ScaledBasisBladeOf::new(scale, UnitBasisBladeOf::from(bitset)).into()
+    }
+
+    #[test]
+    fn exp_bivector_signatures() {
+        // Circular
+        let rotation = exp_bivector::<Euclidean<f64, Const<3>>, _, _>(&blade(0.5, 0b011));
+        assert_approx_eq(rotation.value(), &(blade(0.5f64.cos(), 0b000) + blade(0.5f64.sin(), 0b011)));
+
+        // Hyperbolic, (e0 e1)^2 = 1 in Minkowski space
+        let boost = exp_bivector::<Minkowski, _, _>(&blade(0.5, 0b0011));
+        assert_approx_eq(boost.value(), &(blade(0.5f64.cosh(), 0b0000) + blade(0.5f64.sinh(), 0b0011)));
+
+        // Parabolic, (e1 e0)^2 = 0 in PGA
+        let translation = exp_bivector::<Projective, _, _>(&blade(0.5, 0b1001));
+        assert_approx_eq(translation.value(), &(blade(1.0, 0b0000) + blade(0.5, 0b1001)));
+        assert!(translation.converged());
+        assert_eq!(translation.error_bound(), 0.0);
+    }
+
+    #[test]
+    fn exp_series_matches_closed_form() {
+        let bivector: GeneralMultivector<f64, Const<4>> = blade(2.5, 0b0011) + blade(-1.5, 0b0110);
+        let series = exp::<Minkowski, _, _>(&bivector);
+        let closed_form = exp_bivector::<Minkowski, _, _>(&bivector);
+
+        assert!(series.converged());
+        assert!(series.error_bound() < 1e-9);
+        assert_approx_eq(series.value(), closed_form.value());
+
+        // exp(a) for a scalar
+        let scalar = exp::<Euclidean<f64, Const<3>>, _, _>(&blade(3.0, 0b000));
+        assert!((scalar.value().coefficients()[0] - 3.0f64.exp()).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,133 @@
+use num::Real;
+use dimension::Dimension;
+use unit_basis_blade::UnitBasisBladeOf;
+use general_multivector::GeneralMultivector;
+use ops::Reverse;
+
+impl<R: Real, D: Dimension> GeneralMultivector<R, D> {
+    /// Factorizes a blade of grade `k` into its scale and `k` orthonormal vectors,
+    /// such that the outer product of the vectors multiplied by the scale reproduces the blade.
+    ///
+    /// Uses the algorithm described in _Geometric Algebra for Computer Science_, section 21.6:
+    /// the basis vectors of the basis blade with the largest coefficient are projected
+    /// onto the blade one by one, and each projection is removed from the blade
+    /// before projecting the next one.
+    ///
+    /// Returns `None` if the multivector is zero or not homogeneous. The result for
+    /// a homogeneous multivector which is not a blade is meaningless, see `is_blade`.
+    pub fn factorize(&self) -> Option<(R, Vec<Self>)> {
+        self.factorize_with_epsilon(R::zero())
+    }
+
+    /// Factorizes a blade like `factorize`, but stops at the first projection with a norm
+    /// not exceeding `epsilon`. A nearly degenerate blade then yields fewer factors instead
+    /// of normalizing a projection dominated by rounding errors.
+    pub fn factorize_with_epsilon(&self, epsilon: R) -> Option<(R, Vec<Self>)> {
+        if !self.is_homogeneous() {
+            return None;
+        }
+
+        let largest = self.blades().max_by(|lhs, rhs| {
+            lhs.scale().abs().partial_cmp(&rhs.scale().abs()).unwrap()
+        })?;
+        let mut remainder = self.normalized()?;
+        let mut factors = Vec::new();
+
+        for index in 0..D::to_u8() {
+            if !largest.unit_basis_blade().contains_basis_vector(index) {
+                continue;
+            }
+
+            let basis_vector = GeneralMultivector::from(UnitBasisBladeOf::basis_vector(index));
+            let projection = (basis_vector << &remainder) << remainder.inverse()?;
+
+            if projection.norm() <= epsilon {
+                break;
+            }
+
+            let factor = projection.normalized()?;
+
+            remainder = &factor << remainder;
+            factors.push(factor);
+        }
+
+        let unit_blade = factors.iter()
+            .fold(GeneralMultivector::scalar(R::one()), |product, factor| product ^ factor);
+        let scale = (self * unit_blade.reverse()).coefficients()[0];
+
+        Some((scale, factors))
+    }
+
+    /// Checks whether the multivector is a blade, an outer product of vectors,
+    /// by comparing it to the outer product of its factors. The norm of the difference
+    /// must not exceed `epsilon`.
+    pub fn is_blade(&self, epsilon: R) -> bool {
+        if self.is_zero() {
+            return true;
+        }
+
+        let (scale, factors) = match self.factorize() {
+            Some(factorization) => factorization,
+            None => return false,
+        };
+        let reconstructed = factors.iter()
+            .fold(GeneralMultivector::scalar(scale), |product, factor| product ^ factor);
+
+        (self - reconstructed).norm() <= epsilon
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dimension::Const;
+    use test_util::vector;
+
+    #[test]
+    fn factorize_reproduces_blade() {
+        let blade: GeneralMultivector<f64, Const<4>> = vector(&[1.0, 2.0, 0.0, -1.0])
+            ^ vector(&[0.0, 1.0, 3.0, 0.5])
+            ^ vector(&[2.0, 0.0, 1.0, 1.0]);
+        let (scale, factors) = blade.factorize().unwrap();
+
+        assert_eq!(factors.len(), 3);
+
+        for (i, lhs) in factors.iter().enumerate() {
+            for (j, rhs) in factors.iter().enumerate() {
+                let expected = if i == j { 1.0 } else { 0.0 };
+                assert!(((lhs * rhs).coefficients()[0] - expected).abs() < 1e-9);
+            }
+        }
+
+        let reconstructed = factors.iter()
+            .fold(GeneralMultivector::scalar(scale), |product, factor| product ^ factor);
+        assert!((&blade - reconstructed).norm() < 1e-9);
+
+        let scalar = GeneralMultivector::<f64, Const<3>>::scalar(-2.0).factorize().unwrap();
+        assert_eq!(scalar, (-2.0, Vec::new()));
+    }
+
+    #[test]
+    fn factorize_with_epsilon() {
+        // A homogeneous non-blade, whose second projection is negligible
+        let e12: GeneralMultivector<f64, Const<4>> = UnitBasisBladeOf::from(0b0011u8).into();
+        let e13: GeneralMultivector<f64, Const<4>> = UnitBasisBladeOf::from(0b0101u8).into();
+        let e34: GeneralMultivector<f64, Const<4>> = UnitBasisBladeOf::from(0b1100u8).into();
+        let bivector = &e12 + &e34 + &e13 * 1e-13;
+
+        assert_eq!(bivector.factorize().unwrap().1.len(), 2);
+        assert_eq!(bivector.factorize_with_epsilon(1e-9).unwrap().1.len(), 1);
+    }
+
+    #[test]
+    fn is_blade() {
+        let e12: GeneralMultivector<f64, Const<4>> = UnitBasisBladeOf::from(0b0011u8).into();
+        let e34: GeneralMultivector<f64, Const<4>> = UnitBasisBladeOf::from(0b1100u8).into();
+
+        assert!(e12.is_blade(1e-9));
+        assert!((&e12 + &e34 * 1e-12).is_blade(1e-9));
+        assert!(!(&e12 + &e34).is_blade(1e-9));
+        assert!(vector::<Const<4>>(&[1.0, -2.0, 3.0, 0.0]).is_blade(1e-9));
+        assert!(GeneralMultivector::<f64, Const<4>>::zero().is_blade(1e-9));
+    }
+}
@@ -0,0 +1,522 @@
+use std::ops::{Add, Sub, Neg, Mul, BitXor};
+use std::marker::PhantomData;
+use dimension::{Bitset, Dimension, Grade};
+use num::Real;
+use unit_basis_blade::UnitBasisBladeOf;
+use scaled_basis_blade::ScaledBasisBladeOf;
+use space::{Space, Euclidean};
+use ops::{GeometricProduct, OuterProduct, Dual, Undual, RightComplement, LeftComplement};
+
+/// A dense multivector, storing a coefficient for every one of the `2^D` basis blades.
+/// The coefficients are indexed by the bitset of the corresponding `UnitBasisBladeOf`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct GeneralMultivector<R: Real, D: Dimension> {
+    coefficients: Vec<R>,
+    _marker: PhantomData<D>,
+}
+
+impl<R: Real, D: Dimension> GeneralMultivector<R, D> {
+    /// The number of basis blades of the algebra, `2^D`
+    #[inline]
+    pub fn basis_blade_count() -> usize {
+        1 << D::to_usize()
+    }
+
+    pub fn zero() -> Self {
+        GeneralMultivector {
+            coefficients: vec![R::zero(); Self::basis_blade_count()],
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn scalar(scale: R) -> Self {
+        let mut result = Self::zero();
+        result.coefficients[0] = scale;
+        result
+    }
+
+    /// Creates a multivector from coefficients indexed by blade bitsets.
+    /// Panics if the number of coefficients is not `2^D`.
+    pub fn from_coefficients(coefficients: Vec<R>) -> Self {
+        assert_eq!(coefficients.len(), Self::basis_blade_count(),
+                   "A multivector requires exactly 2^D coefficients.");
+
+        GeneralMultivector {
+            coefficients,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn coefficients(&self) -> &[R] {
+        &self.coefficients
+    }
+
+    pub fn coefficient(&self, unit_basis_blade: &UnitBasisBladeOf<D>) -> R {
+        self.coefficients[unit_basis_blade.bitset().to_usize()]
+    }
+
+    pub fn set_coefficient(&mut self, unit_basis_blade: &UnitBasisBladeOf<D>, scale: R) {
+        self.coefficients[unit_basis_blade.bitset().to_usize()] = scale;
+    }
+
+    /// Adds the blade to the corresponding coefficient
+    pub fn add_blade(&mut self, blade: &ScaledBasisBladeOf<R, D>) {
+        self.coefficients[blade.unit_basis_blade().bitset().to_usize()] += blade.scale();
+    }
+
+    /// Iterates over all blades with a non-zero coefficient, ordered by bitset
+    pub fn blades(&self) -> Blades<R, D> {
+        Blades {
+            multivector: self,
+            index: 0,
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.coefficients.iter().all(|coefficient| coefficient.is_zero())
+    }
+
+    /// The grade projection `<A>_k`, keeping only the blades of grade `k`
+    pub fn grade(&self, k: u8) -> Self {
+        let mut result = Self::zero();
+
+        for blade in self.blades_of_grade(k) {
+            result.add_blade(&blade);
+        }
+
+        result
+    }
+
+    /// A bitmask of the grades present in this multivector,
+    /// the `k`-th bit is set if there is a non-zero blade of grade `k`
+    pub fn grades(&self) -> u64 {
+        self.blades().fold(0, |mask, blade| mask | (1 << blade.grade()))
+    }
+
+    /// Returns `true` if all non-zero blades are of the same grade
+    pub fn is_homogeneous(&self) -> bool {
+        self.grades().count_ones() <= 1
+    }
+
+    /// The squared norm `<A ~A>_0` under the Euclidean metric, see `Space::norm_squared`
+    pub fn norm_squared(&self) -> R {
+        Euclidean::<R, D>::norm_squared(self)
+    }
+
+    /// The norm under the Euclidean metric, see `Space::norm`
+    pub fn norm(&self) -> R {
+        Euclidean::<R, D>::norm(self)
+    }
+
+    /// Scales the multivector to a norm of 1, `None` if the multivector is zero
+    pub fn normalized(&self) -> Option<Self> {
+        Euclidean::<R, D>::normalized(self)
+    }
+
+    /// The inverse under the Euclidean metric, `None` if the multivector is not
+    /// invertible, see `Space::inverse`
+    pub fn inverse(&self) -> Option<Self> {
+        Euclidean::<R, D>::inverse(self)
+    }
+
+    /// The projection onto the subspace of the blade under the Euclidean metric,
+    /// see `Space::project_onto`
+    pub fn project_onto(&self, blade: &Self) -> Option<Self> {
+        Euclidean::<R, D>::project_onto(self, blade)
+    }
+
+    /// The rejection from the subspace of the blade under the Euclidean metric,
+    /// see `Space::reject_from`
+    pub fn reject_from(&self, blade: &Self) -> Option<Self> {
+        Euclidean::<R, D>::reject_from(self, blade)
+    }
+
+    /// The reflection in the subspace of the blade under the Euclidean metric,
+    /// see `Space::reflect_in`
+    pub fn reflect_in(&self, blade: &Self) -> Option<Self> {
+        Euclidean::<R, D>::reflect_in(self, blade)
+    }
+
+    /// The reflection in the subspace dual to the blade under the Euclidean metric,
+    /// see `Space::reflect_in_dual`
+    pub fn reflect_in_dual(&self, blade: &Self) -> Option<Self> {
+        Euclidean::<R, D>::reflect_in_dual(self, blade)
+    }
+
+    /// Iterates over the non-zero blades of grade `k`, ordered by bitset
+    pub fn blades_of_grade(&self, k: u8) -> BladesOfGrade<R, D> {
+        let index = if k as usize <= D::to_usize() {
+            Some((1 << k) - 1)
+        } else {
+            None
+        };
+
+        BladesOfGrade {
+            multivector: self,
+            index,
+        }
+    }
+}
+
+impl<R: Real, D: Dimension> Default for GeneralMultivector<R, D> {
+    fn default() -> Self {
+        Self::zero()
+    }
+}
+
+impl<R: Real, D: Dimension> From<ScaledBasisBladeOf<R, D>> for GeneralMultivector<R, D> {
+    fn from(blade: ScaledBasisBladeOf<R, D>) -> Self {
+        let mut result = Self::zero();
+        result.add_blade(&blade);
+        result
+    }
+}
+
+impl<R: Real, D: Dimension> From<UnitBasisBladeOf<D>> for GeneralMultivector<R, D> {
+    fn from(unit_basis_blade: UnitBasisBladeOf<D>) -> Self {
+        let mut result = Self::zero();
+        result.set_coefficient(&unit_basis_blade, R::one());
+        result
+    }
+}
+
+/// An iterator over the non-zero blades of a `GeneralMultivector`
+pub struct Blades<'a, R: Real + 'a, D: Dimension + 'a> {
+    multivector: &'a GeneralMultivector<R, D>,
+    index: usize,
+}
+
+impl<'a, R: Real, D: Dimension> Iterator for Blades<'a, R, D> {
+    type Item = ScaledBasisBladeOf<R, D>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.index < self.multivector.coefficients.len() {
+            let index = self.index;
+            let scale = self.multivector.coefficients[index];
+            self.index += 1;
+
+            if !scale.is_zero() {
+                return Some(ScaledBasisBladeOf::new(scale, UnitBasisBladeOf::new(Bitset::from_usize(index))));
+            }
+        }
+
+        None
+    }
+}
+
+/// An iterator over the non-zero blades of a single grade of a `GeneralMultivector`
+pub struct BladesOfGrade<'a, R: Real + 'a, D: Dimension + 'a> {
+    multivector: &'a GeneralMultivector<R, D>,
+    /// The next bitset with the correct number of bits set, if any
+    index: Option<usize>,
+}
+
+impl<'a, R: Real, D: Dimension> Iterator for BladesOfGrade<'a, R, D> {
+    type Item = ScaledBasisBladeOf<R, D>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(index) = self.index {
+            if index >= self.multivector.coefficients.len() {
+                self.index = None;
+                break;
+            }
+
+            // Advance to the next larger integer with the same number of bits set
+            self.index = if index == 0 {
+                None
+            } else {
+                let lowest_bit = index & index.wrapping_neg();
+                let ripple = index + lowest_bit;
+
+                Some((((ripple ^ index) >> 2) / lowest_bit) | ripple)
+            };
+
+            let scale = self.multivector.coefficients[index];
+
+            if !scale.is_zero() {
+                return Some(ScaledBasisBladeOf::new(scale, UnitBasisBladeOf::new(Bitset::from_usize(index))));
+            }
+        }
+
+        None
+    }
+}
+
+impl_operator! {
+    operator_type: [Add];
+    inline: [false];
+    operator_fn: add;
+    generics: [R: Real, D: Dimension];
+    header: (GeneralMultivector<R, D>, GeneralMultivector<R, D>) -> GeneralMultivector<R, D>;
+    |&lhs, &rhs| {
+        GeneralMultivector::from_coefficients(
+            lhs.coefficients.iter().zip(rhs.coefficients.iter())
+                .map(|(l, r)| *l + *r)
+                .collect()
+        )
+    }
+}
+
+impl_operator! {
+    operator_type: [Sub];
+    inline: [false];
+    operator_fn: sub;
+    generics: [R: Real, D: Dimension];
+    header: (GeneralMultivector<R, D>, GeneralMultivector<R, D>) -> GeneralMultivector<R, D>;
+    |&lhs, &rhs| {
+        GeneralMultivector::from_coefficients(
+            lhs.coefficients.iter().zip(rhs.coefficients.iter())
+                .map(|(l, r)| *l - *r)
+                .collect()
+        )
+    }
+}
+
+impl_operator! {
+    operator_type: [Mul];
+    inline: [false];
+    operator_fn: mul;
+    generics: [R: Real, D: Dimension];
+    header: (GeneralMultivector<R, D>, R) -> GeneralMultivector<R, D>;
+    |&lhs, &rhs| {
+        GeneralMultivector::from_coefficients(
+            lhs.coefficients.iter()
+                .map(|l| *l * *rhs)
+                .collect()
+        )
+    }
+}
+
+impl_operator_geom! {
+    inline: [false];
+    generics: [R: Real, D: Dimension];
+    header: (GeneralMultivector<R, D>, GeneralMultivector<R, D>) -> GeneralMultivector<R, D>;
+    |&lhs, &rhs| {
+        Euclidean::<R, D>::geom(lhs, rhs)
+    }
+}
+
+impl_operator_outer! {
+    inline: [false];
+    generics: [R: Real, D: Dimension];
+    header: (GeneralMultivector<R, D>, GeneralMultivector<R, D>) -> GeneralMultivector<R, D>;
+    |&lhs, &rhs| {
+        Euclidean::<R, D>::outer(lhs, rhs)
+    }
+}
+
+impl_operators_inner! {
+    inline: [false];
+    generics: [R: Real, D: Dimension];
+    header: (GeneralMultivector<R, D>, GeneralMultivector<R, D>) -> GeneralMultivector<R, D>;
+    |&lhs, &rhs, selection| {
+        Euclidean::<R, D>::product(lhs, rhs, selection)
+    }
+}
+
+impl_unary_operators_involution! {
+    inline: [false];
+    generics: [R: Real, D: Dimension];
+    header: (GeneralMultivector<R, D>) -> GeneralMultivector<R, D>;
+    |&this, negates| {
+        let mut result = this.clone();
+
+        for blade in this.blades() {
+            if negates(blade.grade()) {
+                result.set_coefficient(blade.unit_basis_blade(), blade.scale().neg());
+            }
+        }
+
+        result
+    }
+}
+
+/// Implements a linear unary operator on `GeneralMultivector` by applying it to every blade
+macro_rules! impl_unary_operator_linear {
+    ($operator_type:ident, $operator_fn:ident) => {
+        impl_unary_operator! {
+            operator_type: [$operator_type];
+            inline: [false];
+            operator_fn: $operator_fn;
+            generics: [R: Real, D: Dimension];
+            header: (GeneralMultivector<R, D>) -> GeneralMultivector<R, D>;
+            |&this| {
+                let mut result = GeneralMultivector::zero();
+
+                for blade in this.blades() {
+                    result.add_blade(&$operator_type::$operator_fn(&blade));
+                }
+
+                result
+            }
+        }
+    }
+}
+
+impl_unary_operator_linear!(Dual, dual);
+impl_unary_operator_linear!(Undual, undual);
+impl_unary_operator_linear!(RightComplement, right_complement);
+impl_unary_operator_linear!(LeftComplement, left_complement);
+
+impl_unary_operator! {
+    operator_type: [Neg];
+    inline: [false];
+    operator_fn: neg;
+    generics: [R: Real, D: Dimension];
+    header: (GeneralMultivector<R, D>) -> GeneralMultivector<R, D>;
+    |&this| {
+        GeneralMultivector::from_coefficients(
+            this.coefficients.iter()
+                .map(|coefficient| -*coefficient)
+                .collect()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dimension::Const;
+    use ops::{Reverse, Dual, Undual, RightComplement, LeftComplement};
+
+    #[test]
+    fn general_multivector_add() {
+        let a: GeneralMultivector<f32, Const<3>> = GeneralMultivector::scalar(1.0);
+        let b: GeneralMultivector<f32, Const<3>> = ScaledBasisBladeOf::new(2.0, 0b011.into()).into();
+        let sum = &a + &b;
+
+        assert_eq!(sum.coefficient(&0b000.into()), 1.0);
+        assert_eq!(sum.coefficient(&0b011.into()), 2.0);
+        assert_eq!(sum.blades().count(), 2);
+        assert!((sum.clone() - a - b).is_zero());
+    }
+
+    #[test]
+    fn general_multivector_scalar_mul_neg() {
+        let a: GeneralMultivector<f32, Const<3>> = UnitBasisBladeOf::from(0b101u8).into();
+        let b = -(a * 3.0);
+
+        assert_eq!(b.coefficient(&0b101.into()), -3.0);
+        assert_eq!(b.blades().collect::<Vec<_>>(), vec![ScaledBasisBladeOf::new(-3.0, 0b101.into())]);
+    }
+
+    #[test]
+    fn general_multivector_geometric_product() {
+        // (1 + e12) * (1 + e12) = 1 + 2 e12 + e12 e12 = 2 e12
+        let a: GeneralMultivector<f32, Const<3>> = GeneralMultivector::scalar(1.0)
+            + GeneralMultivector::from(UnitBasisBladeOf::from(0b011u8));
+        let product = &a * &a;
+
+        assert_eq!(product.coefficient(&0b000.into()), 0.0);
+        assert_eq!(product.coefficient(&0b011.into()), 2.0);
+        assert_eq!(product.blades().count(), 1);
+    }
+
+    #[test]
+    fn general_multivector_outer_product() {
+        // (e1 + e2) ^ (e1 - e2) = -2 e12
+        let e1: GeneralMultivector<f32, Const<3>> = UnitBasisBladeOf::from(0b001u8).into();
+        let e2: GeneralMultivector<f32, Const<3>> = UnitBasisBladeOf::from(0b010u8).into();
+        let product = (&e1 + &e2) ^ (&e1 - &e2);
+
+        assert_eq!(product, GeneralMultivector::from(ScaledBasisBladeOf::new(-2.0, 0b011.into())));
+    }
+
+    #[test]
+    fn general_multivector_left_contraction() {
+        // e1 << (1 + e12) = e2
+        let e1: GeneralMultivector<f32, Const<3>> = UnitBasisBladeOf::from(0b001u8).into();
+        let a = GeneralMultivector::scalar(1.0) + GeneralMultivector::from(UnitBasisBladeOf::from(0b011u8));
+
+        assert_eq!(&e1 << &a, GeneralMultivector::from(UnitBasisBladeOf::from(0b010u8)));
+        assert_eq!(&a >> &e1, GeneralMultivector::from(ScaledBasisBladeOf::new(-1.0, 0b010.into())));
+    }
+
+    #[test]
+    fn general_multivector_reverse_sandwich() {
+        // A rotation by 90 degrees in the e12 plane: R = (1 - e12) / sqrt(2)
+        let half_sqrt = 0.5f64.sqrt();
+        let rotor: GeneralMultivector<f64, Const<3>> = GeneralMultivector::scalar(half_sqrt)
+            + GeneralMultivector::from(ScaledBasisBladeOf::new(-half_sqrt, 0b011.into()));
+        let e1: GeneralMultivector<f64, Const<3>> = UnitBasisBladeOf::from(0b001u8).into();
+        let rotated = &rotor * &e1 * rotor.reverse();
+
+        assert!((rotated.coefficient(&0b010.into()) - 1.0).abs() < 1e-10);
+        assert!(rotated.coefficient(&0b001.into()).abs() < 1e-10);
+    }
+
+    #[test]
+    fn general_multivector_grade_projection() {
+        let a: GeneralMultivector<f32, Const<3>> = GeneralMultivector::scalar(1.0)
+            + GeneralMultivector::from(ScaledBasisBladeOf::new(2.0, 0b011.into()))
+            + GeneralMultivector::from(ScaledBasisBladeOf::new(3.0, 0b110.into()))
+            + GeneralMultivector::from(ScaledBasisBladeOf::new(4.0, 0b100.into()));
+        let bivector = a.grade(2);
+
+        assert_eq!(a.grades(), 0b0111);
+        assert!(!a.is_homogeneous());
+        assert!(bivector.is_homogeneous());
+        assert_eq!(bivector.grades(), 0b0100);
+        assert_eq!(bivector.blades().collect::<Vec<_>>(), vec![
+            ScaledBasisBladeOf::new(2.0, 0b011.into()),
+            ScaledBasisBladeOf::new(3.0, 0b110.into()),
+        ]);
+        assert_eq!(a.grade(0), GeneralMultivector::scalar(1.0));
+        assert!(a.grade(3).is_zero());
+        assert!(a.grade(4).is_zero());
+    }
+
+    #[test]
+    fn general_multivector_inverse() {
+        let a: GeneralMultivector<f64, Const<3>> = GeneralMultivector::scalar(2.0)
+            + GeneralMultivector::from(ScaledBasisBladeOf::new(3.0, 0b001.into()));
+        let inverse = a.inverse().unwrap();
+
+        assert_eq!(a.norm_squared(), 13.0);
+        assert!((a.normalized().unwrap().norm() - 1.0).abs() < 1e-12);
+        assert!((&a * &inverse - GeneralMultivector::scalar(1.0)).coefficients().iter().all(|c| c.abs() < 1e-12));
+
+        // 1 + e1 is a zero divisor, (1 + e1)(1 - e1) = 0
+        let zero_divisor: GeneralMultivector<f64, Const<3>> = GeneralMultivector::scalar(1.0)
+            + GeneralMultivector::from(UnitBasisBladeOf::from(0b001u8));
+        assert!(zero_divisor.inverse().is_none());
+    }
+
+    #[test]
+    fn general_multivector_projection_and_reflection() {
+        let vector: GeneralMultivector<f64, Const<3>> = GeneralMultivector::from(ScaledBasisBladeOf::new(1.0, 0b001.into()))
+            + GeneralMultivector::from(ScaledBasisBladeOf::new(2.0, 0b010.into()))
+            + GeneralMultivector::from(ScaledBasisBladeOf::new(3.0, 0b100.into()));
+        // The plane spanned by e1 + e2 and e3
+        let plane = (GeneralMultivector::from(UnitBasisBladeOf::from(0b001u8))
+            + GeneralMultivector::from(UnitBasisBladeOf::from(0b010u8)))
+            ^ GeneralMultivector::from(UnitBasisBladeOf::from(0b100u8));
+        let projection = vector.project_onto(&plane).unwrap();
+        let rejection = vector.reject_from(&plane).unwrap();
+
+        assert_eq!(projection.coefficients(), &[0.0, 1.5, 1.5, 0.0, 3.0, 0.0, 0.0, 0.0]);
+        assert_eq!(rejection.coefficients(), &[0.0, -0.5, 0.5, 0.0, 0.0, 0.0, 0.0, 0.0]);
+        assert_eq!(vector.reflect_in(&plane).unwrap(), &projection - &rejection);
+
+        // Reflecting bivectors in the plane with the normal e3 and in the line e3
+        let e3: GeneralMultivector<f64, Const<3>> = UnitBasisBladeOf::from(0b100u8).into();
+        let e12: GeneralMultivector<f64, Const<3>> = UnitBasisBladeOf::from(0b011u8).into();
+        let e13: GeneralMultivector<f64, Const<3>> = UnitBasisBladeOf::from(0b101u8).into();
+        assert_eq!(e12.reflect_in_dual(&e3).unwrap(), e12);
+        assert_eq!(e13.reflect_in_dual(&e3).unwrap(), -e13.clone());
+        assert_eq!(e12.reflect_in(&e3).unwrap(), e12);
+        assert_eq!(e13.reflect_in(&e3).unwrap(), -e13.clone());
+        assert!(vector.project_onto(&GeneralMultivector::zero()).is_none());
+    }
+
+    #[test]
+    fn general_multivector_dual() {
+        let a: GeneralMultivector<f64, Const<3>> = GeneralMultivector::scalar(2.0)
+            + GeneralMultivector::from(ScaledBasisBladeOf::new(3.0, 0b011.into()));
+
+        assert_eq!(a.clone().dual().undual(), a);
+        assert_eq!(a.clone().right_complement().left_complement(), a);
+        assert_eq!(a.clone().dual().coefficient(&0b111.into()), -2.0);
+        assert_eq!(a.right_complement().coefficient(&0b111.into()), 2.0);
+    }
+}
@@ -2,9 +2,10 @@
 
 extern crate num_traits;
 extern crate typenum;
-extern crate generic_array;
 extern crate alga;
 extern crate nalgebra;
+#[macro_use]
+extern crate lazy_static;
 
 pub mod dimension;
 pub mod space;
@@ -12,11 +13,29 @@ pub mod space;
 pub mod ops;
 pub mod unit_basis_blade;
 pub mod scaled_basis_blade;
+pub mod general_multivector;
+pub mod sparse_multivector;
+pub mod dyn_space;
+pub mod cayley_table;
+pub mod rotor;
+pub mod exponential;
+pub mod meet_join;
+pub mod factorization;
+pub mod models;
 pub mod num;
 
+#[cfg(test)]
+mod test_util;
+
 pub use dimension::*;
 pub use space::*;
 pub use unit_basis_blade::*;
 pub use scaled_basis_blade::*;
+pub use general_multivector::*;
+pub use sparse_multivector::*;
+pub use dyn_space::*;
+pub use cayley_table::*;
+pub use rotor::*;
+pub use exponential::*;
 pub use ops::*;
 pub use num::*;
@@ -0,0 +1,141 @@
+use num::Real;
+use dimension::Dimension;
+use unit_basis_blade::UnitBasisBladeOf;
+use general_multivector::GeneralMultivector;
+use ops::Dual;
+
+impl<R: Real, D: Dimension> GeneralMultivector<R, D> {
+    /// Computes the meet (intersection) and join (union) of two blades, using the algorithm
+    /// by Fontijne described in _Geometric Algebra for Computer Science_, section 21.7.
+    ///
+    /// The meet and join are only defined up to scale. Components with a norm not exceeding
+    /// `epsilon` are considered zero, which makes the computation robust to nearly dependent
+    /// blades. Returns `None` if either multivector is zero or not homogeneous.
+    pub fn meet_and_join(&self, other: &Self, epsilon: R) -> Option<(Self, Self)> {
+        let (a, b) = match (homogeneous_grade(self), homogeneous_grade(other)) {
+            (Some(a), Some(b)) if a <= b => ((self, a), (other, b)),
+            (Some(a), Some(b)) => ((other, b), (self, a)),
+            _ => return None,
+        };
+        let ((a, a_grade), (b, b_grade)) = (a, b);
+        let (delta, delta_grade) = delta_product(a, b, epsilon)?;
+        let meet_grade = (a_grade + b_grade - delta_grade) / 2;
+        let join_grade = (a_grade + b_grade + delta_grade) / 2;
+        let a_inverse = a.inverse()?;
+
+        // The factors of the dual of the delta product span the meet and the orthogonal
+        // complement of the join
+        let mut meet = GeneralMultivector::scalar(R::one());
+        let mut join = GeneralMultivector::from(UnitBasisBladeOf::pseudoscalar());
+        let mut current_meet_grade = 0;
+        let mut current_join_grade = D::to_u8();
+
+        for factor in delta.dual().factorize_with_epsilon(epsilon)?.1 {
+            if current_meet_grade == meet_grade || current_join_grade == join_grade {
+                break;
+            }
+
+            let projection = (&factor << a) << &a_inverse;
+            let rejection = &factor - &projection;
+
+            if projection.norm() > epsilon {
+                meet = meet ^ projection.normalized()?;
+                current_meet_grade += 1;
+            }
+
+            if rejection.norm() > epsilon {
+                join = rejection.normalized()? << join;
+                current_join_grade -= 1;
+            }
+        }
+
+        if current_meet_grade == meet_grade {
+            let join = a ^ (meet.inverse()? << b);
+
+            Some((meet, join))
+        } else if current_join_grade == join_grade {
+            let meet = (b << join.inverse()?) << a;
+
+            Some((meet, join))
+        } else {
+            None
+        }
+    }
+
+    /// The meet (intersection) of two blades, see `meet_and_join`
+    pub fn meet(&self, other: &Self, epsilon: R) -> Option<Self> {
+        self.meet_and_join(other, epsilon).map(|(meet, _)| meet)
+    }
+
+    /// The join (union) of two blades, see `meet_and_join`
+    pub fn join(&self, other: &Self, epsilon: R) -> Option<Self> {
+        self.meet_and_join(other, epsilon).map(|(_, join)| join)
+    }
+}
+
+fn homogeneous_grade<R: Real, D: Dimension>(multivector: &GeneralMultivector<R, D>) -> Option<u8> {
+    if multivector.is_zero() || !multivector.is_homogeneous() {
+        None
+    } else {
+        Some(multivector.grades().trailing_zeros() as u8)
+    }
+}
+
+/// The delta product `A Δ B`, the part of the highest grade of `A B` with a norm exceeding `epsilon`
+fn delta_product<R: Real, D: Dimension>(a: &GeneralMultivector<R, D>, b: &GeneralMultivector<R, D>, epsilon: R)
+        -> Option<(GeneralMultivector<R, D>, u8)> {
+    let product = a * b;
+
+    (0..(D::to_u8() + 1)).rev()
+        .map(|grade| (product.grade(grade), grade))
+        .find(|&(ref part, _)| part.norm() > epsilon)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dimension::Const;
+    use ops::Reverse;
+    use test_util::vector;
+
+    /// Checks that `a` and `b` are proportional blades of the same grade
+    fn assert_proportional<D: Dimension>(a: &GeneralMultivector<f64, D>, b: &GeneralMultivector<f64, D>) {
+        let a = a.normalized().unwrap();
+        let b = b.normalized().unwrap();
+        let scalar = (&a * b.clone().reverse()).coefficients()[0];
+
+        assert!((scalar.abs() - 1.0).abs() < 1e-9, "{:?} is not proportional to {:?}", a.coefficients(), b.coefficients());
+    }
+
+    #[test]
+    fn meet_join_basis_blades() {
+        let e12: GeneralMultivector<f64, Const<3>> = UnitBasisBladeOf::from(0b011u8).into();
+        let e23: GeneralMultivector<f64, Const<3>> = UnitBasisBladeOf::from(0b110u8).into();
+        let (meet, join) = e12.meet_and_join(&e23, 1e-9).unwrap();
+
+        assert_proportional(&meet, &UnitBasisBladeOf::from(0b010u8).into());
+        assert_proportional(&join, &UnitBasisBladeOf::from(0b111u8).into());
+    }
+
+    #[test]
+    fn meet_join_general_position() {
+        // Two planes through the origin intersecting in the line along e1 + e2
+        let a: GeneralMultivector<f64, Const<3>> = vector(&[1.0, 1.0, 0.0]) ^ vector(&[0.0, 0.0, 1.0]);
+        let b: GeneralMultivector<f64, Const<3>> = vector(&[1.0, 0.0, 0.0]) ^ vector(&[0.0, 1.0, 0.0]);
+
+        assert_proportional(&a.meet(&b, 1e-9).unwrap(), &vector(&[1.0, 1.0, 0.0]));
+        assert_proportional(&a.join(&b, 1e-9).unwrap(), &UnitBasisBladeOf::from(0b111u8).into());
+
+        // Two lines in 4D spanning a plane, and a nearly dependent pair of lines
+        let c: GeneralMultivector<f64, Const<4>> = vector(&[1.0, 2.0, 0.0, 1.0]);
+        let d: GeneralMultivector<f64, Const<4>> = vector(&[0.0, 1.0, 3.0, 0.0]);
+        let (meet, join) = c.meet_and_join(&d, 1e-9).unwrap();
+
+        assert_eq!(meet.grades(), 1);
+        assert_proportional(&join, &(&c ^ &d));
+
+        let nearly_c = vector(&[1.0, 2.0, 1e-12, 1.0]);
+        assert_proportional(&c.join(&nearly_c, 1e-9).unwrap(), &c);
+        assert_proportional(&c.meet(&nearly_c, 1e-9).unwrap(), &c);
+    }
+}
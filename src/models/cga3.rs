@@ -0,0 +1,285 @@
+//! The model of 3D conformal geometric algebra, R(4, 1), using the null basis.
+//!
+//! The basis vectors `e1`, `e2`, `e3` (indices 0 to 2) are Euclidean, followed by the null
+//! vectors `e_o` (index 3), the point at the origin, and `e_inf` (index 4), the point
+//! at infinity, where `e_o . e_inf = -1`. The non-diagonal metric is handled by `MetricSpace`.
+//!
+//! Elements are given either in the direct form, as outer products of points
+//! (`round`, `flat`), or in the dual form (`dual_sphere`, `dual_plane`).
+//! The forms are converted using `dual` and `undual`.
+
+use std::marker::PhantomData;
+use nalgebra::DMatrix;
+use num::Real;
+use dimension::{Const, Grade};
+use unit_basis_blade::UnitBasisBladeOf;
+use general_multivector::GeneralMultivector;
+use space::{Space, Metric, MetricSpace, MetricProperties};
+use ops::GradeInvolution;
+
+pub type Multivector<R> = GeneralMultivector<R, Const<5>>;
+
+/// The metric of the null basis `e1`, `e2`, `e3`, `e_o`, `e_inf`
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Cga3Metric<R: Scalar> {
+    _marker: PhantomData<R>,
+}
+
+pub type Cga3Space<R> = MetricSpace<Cga3Metric<R>>;
+
+/// A scalar type with a statically allocated diagonalization of the metric of `Cga3Metric`
+pub trait Scalar: Real {
+    fn cga3_metric_properties() -> &'static MetricProperties<Self>;
+}
+
+macro_rules! impl_scalar {
+    ($($type:ident),*) => {
+        $(
+            impl Scalar for $type {
+                fn cga3_metric_properties() -> &'static MetricProperties<$type> {
+                    lazy_static! {
+                        static ref PROPERTIES: MetricProperties<$type> = MetricProperties::new(DMatrix::from_row_slice(5, 5, &[
+                            1.0, 0.0, 0.0,  0.0,  0.0,
+                            0.0, 1.0, 0.0,  0.0,  0.0,
+                            0.0, 0.0, 1.0,  0.0,  0.0,
+                            0.0, 0.0, 0.0,  0.0, -1.0,
+                            0.0, 0.0, 0.0, -1.0,  0.0,
+                        ]));
+                    }
+
+                    &PROPERTIES
+                }
+            }
+        )*
+    }
+}
+
+impl_scalar!(f32, f64);
+
+impl<R: Scalar> Metric for Cga3Metric<R> {
+    type Dimension = Const<5>;
+    type Scalar = R;
+
+    fn metric_properties() -> &'static MetricProperties<R> {
+        R::cga3_metric_properties()
+    }
+}
+
+fn basis_vector<R: Scalar>(index: u8, scale: R) -> Multivector<R> {
+    let mut result = Multivector::zero();
+    result.set_coefficient(&UnitBasisBladeOf::new(1 << index), scale);
+    result
+}
+
+/// The null vector `e_o`, representing the origin
+pub fn origin<R: Scalar>() -> Multivector<R> {
+    basis_vector(3, R::one())
+}
+
+/// The null vector `e_inf`, representing the point at infinity
+pub fn infinity<R: Scalar>() -> Multivector<R> {
+    basis_vector(4, R::one())
+}
+
+/// The Euclidean vector `x e1 + y e2 + z e3`
+pub fn vector<R: Scalar>(coordinates: [R; 3]) -> Multivector<R> {
+    basis_vector(0, coordinates[0]) + basis_vector(1, coordinates[1]) + basis_vector(2, coordinates[2])
+}
+
+/// Embeds the Euclidean point as the null vector `e_o + x + 1/2 x^2 e_inf`
+pub fn up<R: Scalar>(point: [R; 3]) -> Multivector<R> {
+    let half = R::one() / (R::one() + R::one());
+    let square = point.iter().fold(R::zero(), |sum, &coordinate| sum + coordinate * coordinate);
+
+    origin() + vector(point) + infinity() * (half * square)
+}
+
+/// Projects a (possibly scaled) conformal point back to the Euclidean point,
+/// `None` if the point has zero weight `-e_inf . X`, e.g. for the point at infinity
+pub fn down<R: Scalar>(point: &Multivector<R>) -> Option<[R; 3]> {
+    let weight = -Cga3Space::<R>::scalar_product(&infinity(), &point.grade(1));
+
+    if weight.is_zero() {
+        return None;
+    }
+
+    let coefficients = point.coefficients();
+
+    Some([coefficients[0b001] / weight, coefficients[0b010] / weight, coefficients[0b100] / weight])
+}
+
+/// The dual sphere with the given center and radius, `up(c) - 1/2 r^2 e_inf`.
+/// An imaginary sphere is obtained for a negative squared radius.
+pub fn dual_sphere<R: Scalar>(center: [R; 3], radius_squared: R) -> Multivector<R> {
+    let half = R::one() / (R::one() + R::one());
+
+    up(center) - infinity() * (half * radius_squared)
+}
+
+/// The dual plane `n + d e_inf` with the normal `n` at the distance `d / |n|` from the origin
+pub fn dual_plane<R: Scalar>(normal: [R; 3], distance: R) -> Multivector<R> {
+    vector(normal) + infinity() * distance
+}
+
+/// The direct round through the points, a point pair for 2 points, a circle for 3 points
+/// and a sphere for 4 points
+pub fn round<R: Scalar>(points: &[[R; 3]]) -> Multivector<R> {
+    points.iter().fold(Multivector::scalar(R::one()), |round, &point| round ^ up(point))
+}
+
+/// The direct flat through the points, a flat point for 1 point, a line for 2 points
+/// and a plane for 3 points
+pub fn flat<R: Scalar>(points: &[[R; 3]]) -> Multivector<R> {
+    round(points) ^ infinity()
+}
+
+/// Converts the direct form into the dual form, `X I^-1`
+pub fn dual<R: Scalar>(direct: &Multivector<R>) -> Multivector<R> {
+    Cga3Space::<R>::dual(direct).expect("The pseudoscalar of R(4, 1) is invertible.")
+}
+
+/// Converts the dual form into the direct form, `X I`
+pub fn undual<R: Scalar>(dual: &Multivector<R>) -> Multivector<R> {
+    Cga3Space::<R>::undual(dual)
+}
+
+/// The center of a round in the direct or dual form, computed as the point `X e_inf X`
+pub fn center<R: Scalar>(round: &Multivector<R>) -> Option<[R; 3]> {
+    let product = Cga3Space::<R>::geom(round, &infinity());
+
+    down(&Cga3Space::<R>::geom(&product, round))
+}
+
+/// The squared radius of a round in the direct form, `X X^ / (e_inf ⌋ X)^2`.
+/// Negative for imaginary rounds. `None` for flats, for which the radius is undefined,
+/// recognized by their `round_direction` having a norm not exceeding `epsilon`.
+pub fn radius_squared<R: Scalar>(round: &Multivector<R>, epsilon: R) -> Option<R> {
+    if round_direction(round).norm() <= epsilon {
+        return None;
+    }
+
+    let carrier = Cga3Space::<R>::left_contraction(&infinity(), round);
+    let carrier_square = Cga3Space::<R>::scalar_product(&carrier, &carrier);
+
+    Some(Cga3Space::<R>::scalar_product(round, &round.clone().grade_involution()) / carrier_square)
+}
+
+/// The direction of a flat in the direct form, the Euclidean blade `D` such that
+/// `-e_inf ⌋ X = D ∧ e_inf`
+pub fn flat_direction<R: Scalar>(flat: &Multivector<R>) -> Multivector<R> {
+    euclidean_factor(&-Cga3Space::<R>::left_contraction(&infinity(), flat))
+}
+
+/// The direction of a round in the direct form, the Euclidean blade `D` such that
+/// `-(e_inf ⌋ X) ∧ e_inf = D ∧ e_inf`
+pub fn round_direction<R: Scalar>(round: &Multivector<R>) -> Multivector<R> {
+    euclidean_factor(&(-Cga3Space::<R>::left_contraction(&infinity(), round) ^ infinity()))
+}
+
+/// Extracts `D` from the free blade `D ∧ e_inf`, using `e_o ⌋ (D ∧ e_inf) = (-1)^(k + 1) D`
+fn euclidean_factor<R: Scalar>(free: &Multivector<R>) -> Multivector<R> {
+    let contraction = Cga3Space::<R>::left_contraction(&origin(), free);
+    let mut result = Multivector::zero();
+
+    for blade in contraction.blades() {
+        let grade = blade.unit_basis_blade().grade();
+        let scale = if grade % 2 == 0 { -blade.scale() } else { blade.scale() };
+
+        result.set_coefficient(blade.unit_basis_blade(), scale);
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_util::assert_approx_eq;
+
+    const EPSILON: f64 = 1e-9;
+
+    fn assert_point_eq(actual: Option<[f64; 3]>, expected: [f64; 3]) {
+        let actual = actual.unwrap();
+
+        for i in 0..3 {
+            assert!((actual[i] - expected[i]).abs() < EPSILON, "{:?} != {:?}", actual, expected);
+        }
+    }
+
+    #[test]
+    fn up_and_down() {
+        let point = up([1.0, -2.0, 3.0]);
+
+        assert!(Cga3Space::<f64>::scalar_product(&point, &point).abs() < EPSILON);
+        assert_point_eq(down(&(&point * 2.5)), [1.0, -2.0, 3.0]);
+        assert_eq!(down(&infinity::<f64>()), None);
+
+        // -1/2 of the squared distance between the points
+        let other = up([2.0, 0.0, 1.0]);
+        assert!((Cga3Space::<f64>::scalar_product(&point, &other) + 4.5).abs() < EPSILON);
+    }
+
+    #[test]
+    fn rounds() {
+        let sphere = round(&[[1.0, 0.0, 0.0], [-1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]]);
+        let dual_form = dual(&sphere);
+        let expected = dual_sphere([0.0, 0.0, 0.0], 1.0);
+
+        // The dual form is proportional to the dual sphere
+        let scale = -Cga3Space::<f64>::scalar_product(&infinity(), &dual_form);
+        assert_approx_eq(&(&dual_form * (1.0 / scale)), &expected);
+        assert_approx_eq(&undual(&dual_form), &sphere);
+
+        assert_point_eq(center(&sphere), [0.0, 0.0, 0.0]);
+        assert!((radius_squared(&sphere, EPSILON).unwrap() - 1.0).abs() < EPSILON);
+
+        let circle = round(&[[3.0, 2.0, 1.0], [1.0, 4.0, 1.0], [1.0, 2.0, 1.0]]);
+        assert_point_eq(center(&circle), [2.0, 3.0, 1.0]);
+        assert!((radius_squared(&circle, EPSILON).unwrap() - 2.0).abs() < EPSILON);
+        let direction = round_direction(&circle);
+        assert_eq!(direction.grades(), 1 << 2);
+        assert!(direction.coefficients()[0b011].abs() > EPSILON);
+
+        let pair = round(&[[1.0, 1.0, 1.0], [1.0, 1.0, 5.0]]);
+        assert_point_eq(center(&pair), [1.0, 1.0, 3.0]);
+        assert!((radius_squared(&pair, EPSILON).unwrap() - 4.0).abs() < EPSILON);
+    }
+
+    #[test]
+    fn rounds_far_from_origin() {
+        // The coefficients of the rounds grow with the distance of the center, their direction does not
+        let sphere = round(&[[201.0, 0.0, 0.0], [199.0, 0.0, 0.0], [200.0, 1.0, 0.0], [200.0, 0.0, 1.0]]);
+        assert!((radius_squared(&sphere, EPSILON).unwrap() - 1.0).abs() < 1e-6);
+        assert_eq!(radius_squared(&flat(&[[201.0, 0.0, 0.0], [199.0, 0.5, 0.0]]), EPSILON), None);
+
+        let sphere = round(&[[9.0f32, 0.0, 0.0], [7.0, 0.0, 0.0], [8.0, 1.0, 0.0], [8.0, 0.0, 1.0]]);
+        assert!((radius_squared(&sphere, 1e-4).unwrap() - 1.0).abs() < 1e-3);
+        assert_eq!(radius_squared(&flat(&[[9.0f32, 0.0, 0.0], [7.0, 0.5, 0.0]]), 1e-4), None);
+    }
+
+    #[test]
+    fn flats() {
+        let line = flat(&[[1.0, 2.0, 3.0], [3.0, 2.0, 3.0]]);
+
+        assert_approx_eq(&flat_direction(&line), &vector([2.0, 0.0, 0.0]));
+        assert_eq!(radius_squared(&line, EPSILON), None);
+
+        // Flats through points which are not aligned with the axes
+        assert_eq!(radius_squared(&flat(&[[0.3, -1.7, 2.2], [1.9, 0.4, -0.6]]), EPSILON), None);
+        assert_eq!(radius_squared(&flat(&[[0.3, -1.7, 2.2], [1.9, 0.4, -0.6], [-2.3, 0.7, 1.1]]), EPSILON), None);
+
+        let plane = flat(&[[0.0, 0.0, 2.0], [1.0, 0.0, 2.0], [0.0, 1.0, 2.0]]);
+        let dual_form = dual(&plane);
+        let normal = dual_form.coefficients()[0b100];
+
+        // The dual plane z = 2
+        assert_approx_eq(&(&dual_form * (1.0 / normal)), &dual_plane([0.0, 0.0, 1.0], 2.0));
+        assert_eq!(flat_direction(&plane).grades(), 1 << 2);
+
+        // The meet of the plane and a line, a flat point
+        let vertical = flat(&[[1.0, 1.0, 0.0], [1.0, 1.0, 1.0]]);
+        let intersection = Cga3Space::<f64>::left_contraction(&dual_form, &vertical);
+        let location = Cga3Space::<f64>::left_contraction(&origin(), &intersection);
+        assert_point_eq(down(&location), [1.0, 1.0, 2.0]);
+    }
+}
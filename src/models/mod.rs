@@ -0,0 +1,88 @@
+//! Geometric models built on top of specific spaces
+//!
+//! In the projective models `pga2` and `pga3`, the elements are multivectors of a single grade.
+//! The meet (intersection) is the outer product and the join is the regressive product,
+//! computed using the complement dual.
+
+use num::Real;
+use dimension::Dimension;
+use unit_basis_blade::UnitBasisBladeOf;
+use general_multivector::GeneralMultivector;
+use space::Space;
+use ops::{Reverse, RightComplement, LeftComplement};
+
+/// Defines the element types of a model, each wrapping a multivector of the dimension
+macro_rules! impl_element {
+    ($dimension:ty; $($name:ident: $doc:expr;)*) => {
+        $(
+            #[doc = $doc]
+            #[derive(Clone, PartialEq, Debug)]
+            pub struct $name<R: $crate::num::Real> {
+                multivector: $crate::general_multivector::GeneralMultivector<R, $dimension>,
+            }
+
+            impl<R: $crate::num::Real> $crate::models::Element<R, $dimension> for $name<R> {
+                fn from_multivector_unchecked(
+                        multivector: $crate::general_multivector::GeneralMultivector<R, $dimension>) -> Self {
+                    $name { multivector }
+                }
+
+                fn multivector(&self) -> &$crate::general_multivector::GeneralMultivector<R, $dimension> {
+                    &self.multivector
+                }
+
+                fn into_multivector(self) -> $crate::general_multivector::GeneralMultivector<R, $dimension> {
+                    self.multivector
+                }
+            }
+
+            impl<R: $crate::num::Real> From<$name<R>> for $crate::general_multivector::GeneralMultivector<R, $dimension> {
+                fn from(element: $name<R>) -> Self {
+                    element.multivector
+                }
+            }
+        )*
+    }
+}
+
+pub mod pga2;
+pub mod pga3;
+pub mod cga3;
+pub mod sta;
+
+/// An element of a model, represented by a multivector of the specific grade
+pub trait Element<R: Real, D: Dimension>: Sized {
+    /// Wraps the multivector without checking its grade
+    fn from_multivector_unchecked(multivector: GeneralMultivector<R, D>) -> Self;
+
+    fn multivector(&self) -> &GeneralMultivector<R, D>;
+
+    fn into_multivector(self) -> GeneralMultivector<R, D>;
+}
+
+fn blade<R: Real, D: Dimension>(scale: R, bitset: u8) -> GeneralMultivector<R, D> {
+    let mut result = GeneralMultivector::zero();
+    result.set_coefficient(&UnitBasisBladeOf::from(bitset), scale);
+    result
+}
+
+/// The meet (intersection) of two elements, the outer product
+pub fn meet<R: Real, D: Dimension>(lhs: &GeneralMultivector<R, D>, rhs: &GeneralMultivector<R, D>)
+        -> GeneralMultivector<R, D> {
+    lhs ^ rhs
+}
+
+/// The join of two elements, the regressive product `lhs ∨ rhs`,
+/// computed as the left complement of the outer product of the right complements
+pub fn join<R: Real, D: Dimension>(lhs: &GeneralMultivector<R, D>, rhs: &GeneralMultivector<R, D>)
+        -> GeneralMultivector<R, D> {
+    (lhs.clone().right_complement() ^ rhs.clone().right_complement()).left_complement()
+}
+
+/// Applies the versor to the element by the sandwich product `V x ~V` of the space
+fn sandwich<S: Space, E: Element<S::Scalar, S::Dimension>>(versor: &GeneralMultivector<S::Scalar, S::Dimension>,
+                                                           element: &E) -> E {
+    let product = S::geom(versor, element.multivector());
+
+    E::from_multivector_unchecked(S::geom(&product, &versor.clone().reverse()))
+}
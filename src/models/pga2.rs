@@ -0,0 +1,328 @@
+//! The model of 2D projective geometric algebra, R(2, 0, 1), for planar geometry.
+//!
+//! The basis vectors `e1`, `e2` (indices 0 and 1) are Euclidean and the last basis vector
+//! `e0` (index 2) is null. Lines are vectors and points are bivectors.
+
+use typenum::{U0, U1, U2};
+use num::Real;
+use dimension::Const;
+use unit_basis_blade::UnitBasisBladeOf;
+use general_multivector::GeneralMultivector;
+use exponential::exp_bivector;
+use space::{Space, Signature};
+use ops::Reverse;
+use models::{Element, blade, meet, join, sandwich};
+
+/// The space of 2D projective geometric algebra, R(2, 0, 1)
+pub type Pga2Space<R> = Signature<R, U2, U0, U1>;
+pub type Multivector<R> = GeneralMultivector<R, Const<3>>;
+
+const E1: u8 = 0b001;
+const E2: u8 = 0b010;
+const E0: u8 = 0b100;
+const E12: u8 = 0b011;
+const E10: u8 = 0b101;
+const E20: u8 = 0b110;
+const E120: u8 = 0b111;
+
+impl_element! {
+    Const<3>;
+    Point: "A point, the bivector `w e12 + x e20 - y e10` with the weight `w`";
+    Line: "A line `a x + b y + c = 0`, the vector `a e1 + b e2 + c e0`";
+    Motor: "A motor, an even versor performing a rigid body motion by the sandwich product `M x ~M`";
+}
+
+/// The point with the Cartesian coordinates `(x, y)`
+pub fn point<R: Real>(x: R, y: R) -> Point<R> {
+    Point::new(x, y)
+}
+
+/// The line `a x + b y + c = 0`
+pub fn line<R: Real>(a: R, b: R, c: R) -> Line<R> {
+    Line::new(a, b, c)
+}
+
+impl<R: Real> Point<R> {
+    /// The point with the Cartesian coordinates `(x, y)`, which is the meet
+    /// of the lines `x` and `y` equal to the coordinates
+    pub fn new(x: R, y: R) -> Self {
+        Point {
+            multivector: blade(R::one(), E12) + blade(x, E20) - blade(y, E10),
+        }
+    }
+
+    /// The point at infinity (ideal point) in the direction `(x, y)`
+    pub fn ideal(x: R, y: R) -> Self {
+        Point {
+            multivector: blade(x, E20) - blade(y, E10),
+        }
+    }
+
+    /// The coefficient of `e12`, zero for ideal points
+    pub fn weight(&self) -> R {
+        self.multivector.coefficient(&UnitBasisBladeOf::new(E12))
+    }
+
+    /// The Cartesian coordinates `(x, y)`, `None` for ideal points
+    pub fn to_cartesian(&self) -> Option<[R; 2]> {
+        let weight = self.weight();
+
+        if weight.is_zero() {
+            return None;
+        }
+
+        Some([
+            self.multivector.coefficient(&UnitBasisBladeOf::new(E20)) / weight,
+            -self.multivector.coefficient(&UnitBasisBladeOf::new(E10)) / weight,
+        ])
+    }
+
+    /// The line through both points, directed from `self` towards `other`
+    pub fn join(&self, other: &Point<R>) -> Line<R> {
+        Line { multivector: join(&self.multivector, &other.multivector) }
+    }
+
+    /// The Euclidean distance between the points, `None` if either of them is ideal
+    pub fn distance(&self, other: &Point<R>) -> Option<R> {
+        let lhs = self.to_cartesian()?;
+        let rhs = other.to_cartesian()?;
+        let dx = lhs[0] - rhs[0];
+        let dy = lhs[1] - rhs[1];
+
+        Some((dx * dx + dy * dy).sqrt())
+    }
+}
+
+impl<R: Real> Line<R> {
+    /// The line `a x + b y + c = 0`
+    pub fn new(a: R, b: R, c: R) -> Self {
+        Line {
+            multivector: blade(a, E1) + blade(b, E2) + blade(c, E0),
+        }
+    }
+
+    /// The line through the points `a` and `b`
+    pub fn through(a: &Point<R>, b: &Point<R>) -> Self {
+        a.join(b)
+    }
+
+    /// The coefficients `[a, b, c]` of the equation `a x + b y + c = 0`
+    pub fn to_coefficients(&self) -> [R; 3] {
+        let coefficient = |bitset| self.multivector.coefficient(&UnitBasisBladeOf::new(bitset));
+
+        [coefficient(E1), coefficient(E2), coefficient(E0)]
+    }
+
+    /// The point of intersection of both lines, an ideal point for parallel lines
+    pub fn meet(&self, other: &Line<R>) -> Point<R> {
+        Point { multivector: meet(&self.multivector, &other.multivector) }
+    }
+
+    /// The Euclidean norm of the line, the length of its normal `(a, b)`
+    pub fn norm(&self) -> R {
+        let coefficients = self.to_coefficients();
+
+        (coefficients[0] * coefficients[0] + coefficients[1] * coefficients[1]).sqrt()
+    }
+
+    /// The line scaled to a unit normal, `None` for the line at infinity
+    pub fn normalized(&self) -> Option<Self> {
+        let norm = self.norm();
+
+        if norm.is_zero() {
+            None
+        } else {
+            Some(Line { multivector: &self.multivector * (R::one() / norm) })
+        }
+    }
+
+    /// The signed distance of the point from the line, positive on the side
+    /// the normal `(a, b)` points to. `None` for the line at infinity or an ideal point.
+    pub fn distance(&self, point: &Point<R>) -> Option<R> {
+        let norm = self.norm();
+        let weight = point.weight();
+
+        if norm.is_zero() || weight.is_zero() {
+            return None;
+        }
+
+        let distance = meet(&self.multivector, &point.multivector).coefficient(&UnitBasisBladeOf::new(E120));
+
+        Some(distance / (norm * weight))
+    }
+
+    /// The angle from the direction of `self` to the direction of `other`, in the range `(-π, π]`.
+    /// `None` if either of the lines is the line at infinity.
+    pub fn angle(&self, other: &Line<R>) -> Option<R> {
+        if self.norm().is_zero() || other.norm().is_zero() {
+            return None;
+        }
+
+        let lhs = self.to_coefficients();
+        let rhs = other.to_coefficients();
+        let cross = lhs[0] * rhs[1] - lhs[1] * rhs[0];
+        let dot = lhs[0] * rhs[0] + lhs[1] * rhs[1];
+
+        Some(cross.atan2(dot))
+    }
+}
+
+/// A position and orientation in the plane
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct Pose<R: Real> {
+    pub x: R,
+    pub y: R,
+    /// The counterclockwise angle from the `x` axis
+    pub angle: R,
+}
+
+impl<R: Real> Pose<R> {
+    pub fn new(x: R, y: R, angle: R) -> Self {
+        Pose { x, y, angle }
+    }
+}
+
+impl<R: Real> Motor<R> {
+    pub fn identity() -> Self {
+        Motor { multivector: Multivector::scalar(R::one()) }
+    }
+
+    /// The motor rotating counterclockwise by `angle` about the point.
+    /// `None` for ideal points.
+    pub fn rotation(center: &Point<R>, angle: R) -> Option<Self> {
+        let weight = center.weight();
+
+        if weight.is_zero() {
+            return None;
+        }
+
+        let half_angle = angle / (R::one() + R::one());
+        let bivector = &center.multivector * (-half_angle / weight);
+
+        Some(Motor { multivector: exp_bivector::<Pga2Space<R>, R, Const<3>>(&bivector).into_value() })
+    }
+
+    /// The motor translating by `(x, y)`
+    pub fn translation(x: R, y: R) -> Self {
+        let half = R::one() / (R::one() + R::one());
+
+        Motor {
+            multivector: Multivector::scalar(R::one()) + (blade(x, E10) + blade(y, E20)) * half,
+        }
+    }
+
+    /// The motor moving the origin with the `x` axis onto the pose
+    pub fn from_pose(pose: &Pose<R>) -> Self {
+        let rotation = Self::rotation(&Point::new(R::zero(), R::zero()), pose.angle).unwrap();
+
+        rotation.compose(&Self::translation(pose.x, pose.y))
+    }
+
+    /// The pose the motor moves the origin with the `x` axis onto
+    pub fn to_pose(&self) -> Pose<R> {
+        let origin = self.apply(&Point::new(R::zero(), R::zero())).to_cartesian().unwrap();
+        let x_axis = self.apply(&Point::ideal(R::one(), R::zero()));
+        let direction_x = x_axis.multivector.coefficient(&UnitBasisBladeOf::new(E20));
+        let direction_y = -x_axis.multivector.coefficient(&UnitBasisBladeOf::new(E10));
+
+        Pose::new(origin[0], origin[1], direction_y.atan2(direction_x))
+    }
+
+    /// The motor moving `pose_a` onto `pose_b`
+    pub fn between(pose_a: &Pose<R>, pose_b: &Pose<R>) -> Self {
+        Self::from_pose(pose_a).inverse().compose(&Self::from_pose(pose_b))
+    }
+
+    /// The motor performing the motion of `self` followed by the motion of `other`
+    pub fn compose(&self, other: &Self) -> Self {
+        Motor { multivector: Pga2Space::<R>::geom(&other.multivector, &self.multivector) }
+    }
+
+    /// The motor performing the inverse motion, `~M`
+    pub fn inverse(&self) -> Self {
+        Motor { multivector: self.multivector.clone().reverse() }
+    }
+
+    /// Applies the motion to the element, `M x ~M`
+    pub fn apply<E: Element<R, Const<3>>>(&self, element: &E) -> E {
+        sandwich::<Pga2Space<R>, E>(&self.multivector, element)
+    }
+}
+
+impl<R: Real> Default for Motor<R> {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::PI;
+
+    const EPSILON: f64 = 1e-9;
+
+    fn assert_point_eq(point: &Point<f64>, expected: [f64; 2]) {
+        let actual = point.to_cartesian().unwrap();
+
+        for i in 0..2 {
+            assert!((actual[i] - expected[i]).abs() < EPSILON, "{:?} != {:?}", actual, expected);
+        }
+    }
+
+    fn assert_pose_eq(actual: Pose<f64>, expected: Pose<f64>) {
+        assert!((actual.x - expected.x).abs() < EPSILON, "{:?} != {:?}", actual, expected);
+        assert!((actual.y - expected.y).abs() < EPSILON, "{:?} != {:?}", actual, expected);
+        assert!((actual.angle - expected.angle).abs() < EPSILON, "{:?} != {:?}", actual, expected);
+    }
+
+    #[test]
+    fn incidence_and_measurements() {
+        let a: Point<f64> = point(1.0, 2.0);
+        let b = point(3.0, 2.0);
+        let horizontal = a.join(&b);
+
+        assert_eq!(horizontal.distance(&a), Some(0.0));
+        assert!((horizontal.distance(&point(0.0, 5.0)).unwrap().abs() - 3.0).abs() < EPSILON);
+        assert!((a.distance(&b).unwrap() - 2.0).abs() < EPSILON);
+
+        // The lines x = 2 and y = 2 meet at (2, 2)
+        let vertical = line(1.0, 0.0, -2.0);
+        assert_point_eq(&vertical.meet(&horizontal), [2.0, 2.0]);
+        assert!((line(0.0, 1.0, 0.0).angle(&line(-1.0, 1.0, 0.0)).unwrap() - PI / 4.0).abs() < EPSILON);
+
+        // Parallel lines meet at an ideal point
+        assert_eq!(line(1.0, 0.0, 0.0).meet(&vertical).to_cartesian(), None);
+        assert_eq!(line(0.0, 1.0, 5.0).distance(&point(3.0, -5.0)), Some(0.0));
+        assert_eq!(line(0.0, 2.0, 10.0).distance(&point(3.0, 0.0)), Some(5.0));
+    }
+
+    #[test]
+    fn motors() {
+        let quarter = Motor::rotation(&point(1.0, 0.0), PI / 2.0).unwrap();
+        assert_point_eq(&quarter.apply(&point(2.0, 0.0)), [1.0, 1.0]);
+
+        let translation = Motor::translation(1.0, -2.0);
+        assert_point_eq(&translation.apply(&point(0.5, 0.5)), [1.5, -1.5]);
+        assert_point_eq(&quarter.compose(&translation).apply(&point(2.0, 0.0)), [2.0, -1.0]);
+
+        // Lines are moved along with the points
+        let line = point(2.0, 0.0).join(&point(2.0, 1.0));
+        assert_eq!(quarter.apply(&line).distance(&point(0.0, 1.0)).map(|d| d.abs() < EPSILON), Some(true));
+    }
+
+    #[test]
+    fn motor_between_poses() {
+        let pose_a = Pose::new(1.0, 2.0, PI / 2.0);
+        let pose_b = Pose::new(-3.0, 0.5, -PI / 4.0);
+
+        assert_pose_eq(Motor::from_pose(&pose_a).to_pose(), pose_a);
+
+        let motor = Motor::between(&pose_a, &pose_b);
+        assert_pose_eq(Motor::from_pose(&pose_a).compose(&motor).to_pose(), pose_b);
+
+        // A point one unit ahead of pose A is moved one unit ahead of pose B
+        let ahead = motor.apply(&point(1.0, 3.0));
+        assert_point_eq(&ahead, [-3.0 + 0.5f64.sqrt(), 0.5 - 0.5f64.sqrt()]);
+    }
+}
@@ -0,0 +1,337 @@
+//! The model of 3D projective geometric algebra, R(3, 0, 1).
+//!
+//! The basis vectors `e1`, `e2`, `e3` (indices 0 to 2) are Euclidean and the last basis
+//! vector `e0` (index 3) is null. Planes are vectors, lines are bivectors and points are
+//! trivectors.
+
+use typenum::{U0, U1, U3};
+use num::Real;
+use dimension::Const;
+use unit_basis_blade::UnitBasisBladeOf;
+use general_multivector::GeneralMultivector;
+use exponential::{Approximation, exp_bivector};
+use space::{Space, Signature};
+use ops::Reverse;
+use models::{Element, blade, meet, join, sandwich};
+
+/// The space of 3D projective geometric algebra, R(3, 0, 1)
+pub type Pga3Space<R> = Signature<R, U3, U0, U1>;
+pub type Multivector<R> = GeneralMultivector<R, Const<4>>;
+
+const E1: u8 = 0b0001;
+const E2: u8 = 0b0010;
+const E3: u8 = 0b0100;
+const E0: u8 = 0b1000;
+const E123: u8 = 0b0111;
+const E120: u8 = 0b1011;
+const E130: u8 = 0b1101;
+const E230: u8 = 0b1110;
+
+impl_element! {
+    Const<4>;
+    Point: "A point, the trivector `w e123 - x e230 + y e130 - z e120` with the weight `w`";
+    Line: "A line, a bivector";
+    Plane: "A plane `a x + b y + c z + d = 0`, the vector `a e1 + b e2 + c e3 + d e0`";
+    Motor: "A motor, an even versor performing a rigid body motion by the sandwich product `M x ~M`";
+    Translator: "A translator, a motor performing a translation";
+}
+
+/// The point with the Cartesian coordinates `(x, y, z)`
+pub fn point<R: Real>(x: R, y: R, z: R) -> Point<R> {
+    Point::new(x, y, z)
+}
+
+/// The plane `a x + b y + c z + d = 0`
+pub fn plane<R: Real>(a: R, b: R, c: R, d: R) -> Plane<R> {
+    Plane::new(a, b, c, d)
+}
+
+impl<R: Real> Point<R> {
+    /// The point with the Cartesian coordinates `(x, y, z)`, which is the meet
+    /// of the planes `x`, `y` and `z` equal to the coordinates
+    pub fn new(x: R, y: R, z: R) -> Self {
+        Point {
+            multivector: blade(R::one(), E123) - blade(x, E230) + blade(y, E130) - blade(z, E120),
+        }
+    }
+
+    /// The point at infinity (ideal point) in the direction `(x, y, z)`
+    pub fn ideal(x: R, y: R, z: R) -> Self {
+        Point {
+            multivector: blade(-x, E230) + blade(y, E130) - blade(z, E120),
+        }
+    }
+
+    /// The coefficient of `e123`, zero for ideal points
+    pub fn weight(&self) -> R {
+        self.multivector.coefficient(&UnitBasisBladeOf::new(E123))
+    }
+
+    /// The Cartesian coordinates `(x, y, z)`, `None` for ideal points
+    pub fn to_cartesian(&self) -> Option<[R; 3]> {
+        let weight = self.weight();
+
+        if weight.is_zero() {
+            return None;
+        }
+
+        Some([
+            -self.multivector.coefficient(&UnitBasisBladeOf::new(E230)) / weight,
+            self.multivector.coefficient(&UnitBasisBladeOf::new(E130)) / weight,
+            -self.multivector.coefficient(&UnitBasisBladeOf::new(E120)) / weight,
+        ])
+    }
+
+    /// The line through both points, directed from `self` towards `other`
+    pub fn join(&self, other: &Point<R>) -> Line<R> {
+        Line { multivector: join(&self.multivector, &other.multivector) }
+    }
+}
+
+impl<R: Real> Line<R> {
+    /// The line through the points `a` and `b`
+    pub fn through(a: &Point<R>, b: &Point<R>) -> Self {
+        a.join(b)
+    }
+
+    /// The plane containing the line and the point
+    pub fn join(&self, point: &Point<R>) -> Plane<R> {
+        Plane { multivector: join(&self.multivector, &point.multivector) }
+    }
+
+    /// The point of intersection of the line and the plane
+    pub fn meet(&self, plane: &Plane<R>) -> Point<R> {
+        Point { multivector: meet(&self.multivector, &plane.multivector) }
+    }
+
+    /// The Euclidean norm of the line, computed from the coefficients of `e12`, `e13` and `e23`
+    pub fn norm(&self) -> R {
+        [0b0011, 0b0101, 0b0110].iter()
+            .map(|&bitset| self.multivector.coefficient(&UnitBasisBladeOf::new(bitset)))
+            .fold(R::zero(), |sum, coefficient| sum + coefficient * coefficient)
+            .sqrt()
+    }
+
+    /// The line scaled to a unit Euclidean norm, `None` for lines at infinity
+    pub fn normalized(&self) -> Option<Self> {
+        let norm = self.norm();
+
+        if norm.is_zero() {
+            None
+        } else {
+            Some(Line { multivector: &self.multivector * (R::one() / norm) })
+        }
+    }
+}
+
+impl<R: Real> Plane<R> {
+    /// The plane `a x + b y + c z + d = 0`
+    pub fn new(a: R, b: R, c: R, d: R) -> Self {
+        Plane {
+            multivector: blade(a, E1) + blade(b, E2) + blade(c, E3) + blade(d, E0),
+        }
+    }
+
+    /// The coefficients `[a, b, c, d]` of the equation `a x + b y + c z + d = 0`
+    pub fn to_coefficients(&self) -> [R; 4] {
+        let coefficient = |bitset| self.multivector.coefficient(&UnitBasisBladeOf::new(bitset));
+
+        [coefficient(E1), coefficient(E2), coefficient(E3), coefficient(E0)]
+    }
+
+    /// The line of intersection of both planes
+    pub fn meet(&self, other: &Plane<R>) -> Line<R> {
+        Line { multivector: meet(&self.multivector, &other.multivector) }
+    }
+
+    /// The signed distance of the point from the plane, assuming a plane with a unit normal
+    /// and a point with a unit weight. Zero if the point lies in the plane.
+    pub fn distance(&self, point: &Point<R>) -> R {
+        meet(&self.multivector, &point.multivector).coefficients()[0b1111]
+    }
+}
+
+impl<R: Real> Translator<R> {
+    /// The translator moving points by `(x, y, z)`
+    pub fn new(x: R, y: R, z: R) -> Self {
+        let half = R::one() / (R::one() + R::one());
+
+        Translator {
+            multivector: Multivector::scalar(R::one())
+                + (blade(x, E1 | E0) + blade(y, E2 | E0) + blade(z, E3 | E0)) * half,
+        }
+    }
+
+    /// Applies the translation to the element, `T x ~T`
+    pub fn apply<E: Element<R, Const<4>>>(&self, element: &E) -> E {
+        sandwich::<Pga3Space<R>, E>(&self.multivector, element)
+    }
+}
+
+/// Computes the logarithm of a normalized motor (`M ~M = 1`) of 3D PGA, the bivector
+/// `B` such that `exp(B) = M`. Returns `None` for motors with a scalar part of `-1`,
+/// which are rotations by a full turn about an undefined line.
+pub fn log_motor_pga3<R: Real>(motor: &Multivector<R>) -> Option<Approximation<R, Const<4>>> {
+    let one = R::one();
+    let scalar = motor.coefficients()[0];
+    let bivector = motor.grade(2);
+
+    // A pure translation `1 + B`, or the identity
+    if scalar >= one {
+        return Some(Approximation::exact(bivector));
+    }
+
+    if scalar <= -one {
+        return None;
+    }
+
+    let pseudoscalar = motor.grade(4);
+    let inverse_sine_squared = one / (one - scalar * scalar);
+    let rotation_scale = scalar.acos() * inverse_sine_squared.sqrt();
+    let translation_scale = inverse_sine_squared * (one - scalar * rotation_scale);
+    let translation = Pga3Space::<R>::geom(&bivector, &pseudoscalar);
+
+    Some(Approximation::exact(&bivector * rotation_scale - translation * translation_scale))
+}
+
+impl<R: Real> Motor<R> {
+    pub fn identity() -> Self {
+        Motor { multivector: Multivector::scalar(R::one()) }
+    }
+
+    /// The motor rotating by `angle` about the line, counterclockwise when looking
+    /// against the direction of the line. `None` for lines at infinity.
+    pub fn from_rotation(line: &Line<R>, angle: R) -> Option<Self> {
+        let half_angle = angle / (R::one() + R::one());
+        let bivector = line.normalized()?.multivector * half_angle;
+
+        Some(Motor { multivector: exp_bivector::<Pga3Space<R>, R, Const<4>>(&bivector).into_value() })
+    }
+
+    /// The motor `exp(B)` of a bivector `B`, as returned by `log`
+    pub fn exp(bivector: &Multivector<R>) -> Self {
+        Motor { multivector: exp_bivector::<Pga3Space<R>, R, Const<4>>(&bivector.grade(2)).into_value() }
+    }
+
+    /// The bivector `B` such that `exp(B)` is this motor, see `log_motor_pga3`
+    pub fn log(&self) -> Option<Multivector<R>> {
+        log_motor_pga3(&self.multivector).map(|log| log.into_value())
+    }
+
+    /// The motor performing the motion of `self` followed by the motion of `other`
+    pub fn compose(&self, other: &Self) -> Self {
+        Motor { multivector: Pga3Space::<R>::geom(&other.multivector, &self.multivector) }
+    }
+
+    /// The motor performing the inverse motion, `~M`
+    pub fn inverse(&self) -> Self {
+        Motor { multivector: self.multivector.clone().reverse() }
+    }
+
+    /// Applies the motion to the element, `M x ~M`
+    pub fn apply<E: Element<R, Const<4>>>(&self, element: &E) -> E {
+        sandwich::<Pga3Space<R>, E>(&self.multivector, element)
+    }
+}
+
+impl<R: Real> Default for Motor<R> {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl<R: Real> From<Translator<R>> for Motor<R> {
+    fn from(translator: Translator<R>) -> Self {
+        Motor { multivector: translator.multivector }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::PI;
+
+    fn assert_point_eq(point: &Point<f64>, expected: [f64; 3]) {
+        let actual = point.to_cartesian().unwrap();
+
+        for i in 0..3 {
+            assert!((actual[i] - expected[i]).abs() < 1e-9, "{:?} != {:?}", actual, expected);
+        }
+    }
+
+    #[test]
+    fn cartesian_round_trip() {
+        assert_point_eq(&point(1.0, -2.0, 3.5), [1.0, -2.0, 3.5]);
+        assert_eq!(Point::<f64>::ideal(1.0, 0.0, 0.0).to_cartesian(), None);
+        assert_eq!(plane(1.0, 2.0, 3.0, 4.0).to_coefficients(), [1.0, 2.0, 3.0, 4.0]);
+
+        // The meet of the planes `x = 1`, `y = 2` and `z = 3`
+        let meet = plane(1.0, 0.0, 0.0, -1.0).meet(&plane(0.0, 1.0, 0.0, -2.0))
+            .meet(&plane(0.0, 0.0, 1.0, -3.0));
+        assert_point_eq(&meet, [1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn join_and_meet() {
+        let a: Point<f64> = point(1.0, 0.0, 0.0);
+        let b = point(0.0, 1.0, 0.0);
+        let c = point(0.0, 0.0, 1.0);
+        let plane = a.join(&b).join(&c);
+
+        for point in &[&a, &b, &c] {
+            assert!(plane.distance(point).abs() < 1e-9);
+        }
+
+        // The plane x + y + z = 1, up to scale
+        let coefficients: [f64; 4] = plane.to_coefficients();
+        assert!((coefficients[0] - coefficients[1]).abs() < 1e-9);
+        assert!((coefficients[0] - coefficients[2]).abs() < 1e-9);
+        assert!((coefficients[0] + coefficients[3]).abs() < 1e-9);
+
+        // The line through a and b intersects the plane x = y at (0.5, 0.5, 0)
+        let intersection = Line::through(&a, &b).meet(&super::plane(1.0, -1.0, 0.0, 0.0));
+        assert_point_eq(&intersection, [0.5, 0.5, 0.0]);
+    }
+
+    #[test]
+    fn motors() {
+        let p = point(1.0, 2.0, 3.0);
+        let translator = Translator::new(1.0, -1.0, 0.5);
+
+        assert_point_eq(&translator.apply(&p), [2.0, 1.0, 3.5]);
+
+        // A quarter turn about the z axis, followed by the translation
+        let z_axis = point(0.0, 0.0, 0.0).join(&point(0.0, 0.0, 1.0));
+        let rotation = Motor::from_rotation(&z_axis, PI / 2.0).unwrap();
+        assert_point_eq(&rotation.apply(&point(1.0, 0.0, 0.0)), [0.0, 1.0, 0.0]);
+
+        let motor = rotation.compose(&translator.clone().into());
+        assert_point_eq(&motor.apply(&p), [-1.0, 0.0, 3.5]);
+        assert_point_eq(&motor.inverse().apply(&motor.apply(&p)), [1.0, 2.0, 3.0]);
+
+        // The rotation about a line not passing through the origin
+        let offset_axis = point(1.0, 0.0, 0.0).join(&point(1.0, 0.0, 1.0));
+        let offset_rotation = Motor::from_rotation(&offset_axis, PI).unwrap();
+        assert_point_eq(&offset_rotation.apply(&point(0.0, 0.0, 2.0)), [2.0, 0.0, 2.0]);
+
+        let log = motor.log().unwrap();
+        assert_point_eq(&Motor::exp(&log).apply(&p), [-1.0, 0.0, 3.5]);
+    }
+
+    #[test]
+    fn log_motor_round_trip() {
+        use exponential::exp;
+
+        // A screw motion: rotation in the e12 plane combined with a translation along e3
+        let bivector: Multivector<f64> = blade(0.7, E1 | E2) + blade(0.4, E3 | E0) + blade(-0.2, E1 | E0);
+        let motor = exp::<Pga3Space<f64>, _, _>(&bivector);
+        let log = log_motor_pga3(motor.value()).unwrap();
+
+        assert!(motor.converged());
+        assert!((log.value() - &bivector).norm() < 1e-9);
+
+        let translator = exp_bivector::<Pga3Space<f64>, _, _>(&blade(0.3, E2 | E0));
+        assert!((log_motor_pga3(translator.value()).unwrap().value() - blade(0.3, E2 | E0)).norm() < 1e-9);
+        assert!(log_motor_pga3(&blade(-1.0, 0b0000)).is_none());
+    }
+}
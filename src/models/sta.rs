@@ -0,0 +1,233 @@
+//! The model of spacetime algebra, R(1, 3), in units where `c = 1`.
+//!
+//! The basis vector `γ0` (index 0) is timelike and squares to `1`, the basis vectors
+//! `γ1`, `γ2`, `γ3` (indices 1 to 3) are spacelike and square to `-1`. With respect to the
+//! observer `γ0`, the relative vectors of the observer's space are the bivectors
+//! `σk = γk γ0`, which square to `1`.
+
+use typenum::{U0, U1, U3};
+use num::Real;
+use dimension::Const;
+use unit_basis_blade::UnitBasisBladeOf;
+use general_multivector::GeneralMultivector;
+use exponential::exp_bivector;
+use space::{Space, Signature};
+use ops::Reverse;
+
+pub type StaSpace<R> = Signature<R, U1, U3, U0>;
+pub type Multivector<R> = GeneralMultivector<R, Const<4>>;
+
+/// The basis vector `γk`
+pub fn gamma<R: Real>(index: u8) -> Multivector<R> {
+    assert!(index < 4, "The basis vector index must be less than 4.");
+
+    UnitBasisBladeOf::new(1 << index).into()
+}
+
+/// The unit pseudoscalar `I = γ0123`, which squares to `-1`
+pub fn pseudoscalar<R: Real>() -> Multivector<R> {
+    UnitBasisBladeOf::pseudoscalar().into()
+}
+
+/// The event with the time `t` and the position `(x, y, z)`, `t γ0 + x γ1 + y γ2 + z γ3`
+pub fn event<R: Real>(t: R, x: R, y: R, z: R) -> Multivector<R> {
+    gamma(0) * t + gamma(1) * x + gamma(2) * y + gamma(3) * z
+}
+
+/// The relative vector `x σ1 + y σ2 + z σ3` with respect to the observer `γ0`
+pub fn relative_vector<R: Real>(components: [R; 3]) -> Multivector<R> {
+    (0..3).fold(Multivector::zero(), |sum, k| {
+        sum + StaSpace::<R>::geom(&gamma(k as u8 + 1), &gamma(0)) * components[k]
+    })
+}
+
+/// The components `(x, y, z)` of a relative vector with respect to the observer `γ0`
+pub fn relative_components<R: Real>(relative_vector: &Multivector<R>) -> [R; 3] {
+    // `σk = γk γ0 = -γ0k`
+    let component = |k: u8| -relative_vector.coefficient(&UnitBasisBladeOf::new(1 | 1 << k));
+
+    [component(1), component(2), component(3)]
+}
+
+/// Splits the vector into the time component `p . v` and the relative vector `p ∧ v`
+/// with respect to the observer with the unit velocity `v`, such that `p v = p . v + p ∧ v`
+pub fn split<R: Real>(vector: &Multivector<R>, observer: &Multivector<R>) -> (R, Multivector<R>) {
+    (StaSpace::<R>::scalar_product(vector, observer), vector ^ observer)
+}
+
+/// The electromagnetic field bivector `F = E + I B` with respect to the observer `γ0`
+pub fn field<R: Real>(electric: [R; 3], magnetic: [R; 3]) -> Multivector<R> {
+    relative_vector(electric) + StaSpace::<R>::geom(&pseudoscalar(), &relative_vector(magnetic))
+}
+
+/// Splits the electromagnetic field bivector into the electric and magnetic field
+/// with respect to the observer `γ0`, using `E = 1/2 (F - γ0 F γ0)` and `I B = 1/2 (F + γ0 F γ0)`
+pub fn field_components<R: Real>(field: &Multivector<R>) -> ([R; 3], [R; 3]) {
+    let half = R::one() / (R::one() + R::one());
+    let reflected = StaSpace::<R>::geom(&StaSpace::<R>::geom(&gamma(0), field), &gamma(0));
+    let electric = (field - &reflected) * half;
+    let magnetic = -StaSpace::<R>::geom(&pseudoscalar(), &((field + reflected) * half));
+
+    (relative_components(&electric), relative_components(&magnetic))
+}
+
+/// The Lorentz invariants of the electromagnetic field, `F^2 = (E^2 - B^2) + 2 I (E . B)`,
+/// returned as `(E^2 - B^2, E . B)`
+pub fn field_invariants<R: Real>(field: &Multivector<R>) -> (R, R) {
+    let two = R::one() + R::one();
+    let square = StaSpace::<R>::geom(field, field);
+
+    (square.coefficients()[0], square.coefficients()[0b1111] / two)
+}
+
+/// A boost, a Lorentz rotor changing the velocity of the observer,
+/// applied by the sandwich product `L x ~L`
+#[derive(Clone, PartialEq, Debug)]
+pub struct Boost<R: Real> {
+    multivector: Multivector<R>,
+}
+
+impl<R: Real> Boost<R> {
+    pub fn identity() -> Self {
+        Boost { multivector: Multivector::scalar(R::one()) }
+    }
+
+    /// The boost with the `rapidity` in the relative direction with respect to `γ0`,
+    /// `exp(rapidity/2 n)` for the unit relative vector `n`. It maps `γ0` to
+    /// `cosh(rapidity) γ0 + sinh(rapidity) n γ0`. `None` for a zero direction.
+    pub fn new(direction: [R; 3], rapidity: R) -> Option<Self> {
+        let direction = relative_vector(direction);
+        let length = direction.norm();
+
+        if length.is_zero() {
+            return None;
+        }
+
+        let half_rapidity = rapidity / (R::one() + R::one());
+        let bivector = direction * (half_rapidity / length);
+
+        Some(Boost { multivector: exp_bivector::<StaSpace<R>, R, Const<4>>(&bivector).into_value() })
+    }
+
+    /// The boost to the frame moving with the velocity `(x, y, z)` relative to `γ0`,
+    /// in units of the speed of light. `None` unless the speed is less than `1`.
+    pub fn from_velocity(velocity: [R; 3]) -> Option<Self> {
+        let speed = velocity.iter().fold(R::zero(), |sum, &v| sum + v * v).sqrt();
+
+        if speed >= R::one() {
+            return None;
+        }
+
+        if speed.is_zero() {
+            return Some(Self::identity());
+        }
+
+        Self::new(velocity, speed.atanh())
+    }
+
+    /// Applies the boost to the multivector, `L x ~L`
+    pub fn apply(&self, multivector: &Multivector<R>) -> Multivector<R> {
+        let product = StaSpace::<R>::geom(&self.multivector, multivector);
+
+        StaSpace::<R>::geom(&product, &self.multivector.clone().reverse())
+    }
+
+    /// The boost performing `self` followed by `other`
+    pub fn compose(&self, other: &Self) -> Self {
+        Boost { multivector: StaSpace::<R>::geom(&other.multivector, &self.multivector) }
+    }
+
+    /// The inverse boost, `~L`
+    pub fn inverse(&self) -> Self {
+        Boost { multivector: self.multivector.clone().reverse() }
+    }
+
+    pub fn multivector(&self) -> &Multivector<R> {
+        &self.multivector
+    }
+
+    pub fn into_multivector(self) -> Multivector<R> {
+        self.multivector
+    }
+}
+
+impl<R: Real> Default for Boost<R> {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl<R: Real> From<Boost<R>> for Multivector<R> {
+    fn from(boost: Boost<R>) -> Self {
+        boost.multivector
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_util::assert_approx_eq;
+
+    const EPSILON: f64 = 1e-9;
+
+    fn assert_components_eq(a: [f64; 3], b: [f64; 3]) {
+        for i in 0..3 {
+            assert!((a[i] - b[i]).abs() < EPSILON, "{:?} != {:?}", a, b);
+        }
+    }
+
+    #[test]
+    fn relative_split() {
+        let event = event::<f64>(2.0, 1.0, -3.0, 0.5);
+        let (time, relative) = split(&event, &gamma(0));
+
+        assert!((time - 2.0).abs() < EPSILON);
+        assert_components_eq(relative_components(&relative), [1.0, -3.0, 0.5]);
+        assert_approx_eq(&relative, &relative_vector([1.0, -3.0, 0.5]));
+
+        // The relative vectors square to 1 and the squared interval is t^2 - x^2
+        let sigma = relative_vector::<f64>([1.0, 0.0, 0.0]);
+        assert_approx_eq(&StaSpace::<f64>::geom(&sigma, &sigma), &Multivector::scalar(1.0));
+        assert!((StaSpace::<f64>::scalar_product(&event, &event) - (4.0 - 10.25)).abs() < EPSILON);
+    }
+
+    #[test]
+    fn boosts() {
+        let rapidity = 0.8f64;
+        let boost = Boost::new([1.0, 0.0, 0.0], rapidity).unwrap();
+        let expected = gamma(0) * rapidity.cosh() + gamma(1) * rapidity.sinh();
+
+        assert_approx_eq(&boost.apply(&gamma(0)), &expected);
+        assert_approx_eq(&boost.apply(&gamma(2)), &gamma(2));
+
+        // Rapidities along the same direction add up
+        let other = Boost::new([2.0, 0.0, 0.0], 0.3).unwrap();
+        assert_approx_eq(boost.compose(&other).multivector(), Boost::new([1.0, 0.0, 0.0], 1.1).unwrap().multivector());
+        assert_approx_eq(&boost.compose(&boost.inverse()).into_multivector(), &Multivector::scalar(1.0));
+
+        let (time, relative) = split(&Boost::<f64>::from_velocity([0.0, 0.6, 0.0]).unwrap().apply(&gamma(0)), &gamma(0));
+        assert!((time - 1.25).abs() < EPSILON);
+        assert_components_eq(relative_components(&relative), [0.0, 0.75, 0.0]);
+        assert!(Boost::<f64>::from_velocity([1.0, 0.0, 0.0]).is_none());
+    }
+
+    #[test]
+    fn electromagnetic_field() {
+        let field = field::<f64>([1.0, 2.0, 0.0], [0.0, -1.0, 3.0]);
+        let (electric, magnetic) = field_components(&field);
+
+        assert_components_eq(electric, [1.0, 2.0, 0.0]);
+        assert_components_eq(magnetic, [0.0, -1.0, 3.0]);
+
+        let (difference, dot) = field_invariants(&field);
+        assert!((difference - (5.0 - 10.0)).abs() < EPSILON);
+        assert!((dot + 2.0).abs() < EPSILON);
+
+        // The invariants are preserved by boosts, while the fields mix
+        let boosted = Boost::new([0.0, 0.0, 1.0], 0.5).unwrap().apply(&field);
+        let (boosted_difference, boosted_dot) = field_invariants(&boosted);
+        assert!((boosted_difference - difference).abs() < EPSILON);
+        assert!((boosted_dot - dot).abs() < EPSILON);
+        assert!((field_components(&boosted).0[0] - 1.0).abs() > EPSILON);
+    }
+}
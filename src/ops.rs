@@ -1,7 +1,7 @@
 use std::ops::Mul;
 use dimension::{Dimension, DimensionBitset};
-use unit_basis_blade::UnitBasisBlade;
-use scaled_basis_blade::ScaledBasisBlade;
+use unit_basis_blade::UnitBasisBladeOf;
+use scaled_basis_blade::ScaledBasisBladeOf;
 use num::Real;
 
 pub trait GeometricProduct<RHS=Self> {
@@ -16,6 +16,136 @@ pub trait OuterProduct<RHS=Self> {
     fn outer(self, rhs: RHS) -> Self::Output;
 }
 
+pub trait LeftContraction<RHS=Self> {
+    type Output;
+
+    fn left_contraction(self, rhs: RHS) -> Self::Output;
+}
+
+pub trait RightContraction<RHS=Self> {
+    type Output;
+
+    fn right_contraction(self, rhs: RHS) -> Self::Output;
+}
+
+pub trait ScalarProduct<RHS=Self> {
+    type Output;
+
+    fn scalar_product(self, rhs: RHS) -> Self::Output;
+}
+
+pub trait HestenesInner<RHS=Self> {
+    type Output;
+
+    fn hestenes_inner(self, rhs: RHS) -> Self::Output;
+}
+
+pub trait FatDot<RHS=Self> {
+    type Output;
+
+    fn fat_dot(self, rhs: RHS) -> Self::Output;
+}
+
+pub trait Reverse {
+    type Output;
+
+    fn reverse(self) -> Self::Output;
+}
+
+pub trait GradeInvolution {
+    type Output;
+
+    fn grade_involution(self) -> Self::Output;
+}
+
+pub trait CliffordConjugate {
+    type Output;
+
+    fn clifford_conjugate(self) -> Self::Output;
+}
+
+/// The dual `A << I^-1` with respect to the pseudoscalar `I`
+pub trait Dual {
+    type Output;
+
+    fn dual(self) -> Self::Output;
+}
+
+/// The inverse of the dual, `A << I`
+pub trait Undual {
+    type Output;
+
+    fn undual(self) -> Self::Output;
+}
+
+/// The metric-independent right complement `C(A)`, satisfying `A ^ C(A) = I`
+/// for every basis blade `A`
+pub trait RightComplement {
+    type Output;
+
+    fn right_complement(self) -> Self::Output;
+}
+
+/// The metric-independent left complement `C(A)`, satisfying `C(A) ^ A = I`
+/// for every basis blade `A`; the inverse of the right complement
+pub trait LeftComplement {
+    type Output;
+
+    fn left_complement(self) -> Self::Output;
+}
+
+/// Determines whether a blade of the given grade is negated by a grade-based involution
+pub type InvolutionSign = fn(u8) -> bool;
+
+/// The reverse negates blades for which `k(k - 1)/2` is odd
+pub fn reverse_negates(grade: u8) -> bool {
+    (grade / 2) % 2 == 1
+}
+
+/// The grade involution negates blades of odd grade
+pub fn grade_involution_negates(grade: u8) -> bool {
+    grade % 2 == 1
+}
+
+/// The Clifford conjugate negates blades for which `k(k + 1)/2` is odd
+pub fn clifford_conjugate_negates(grade: u8) -> bool {
+    ((grade + 1) / 2) % 2 == 1
+}
+
+/// Selects the grade of the part of the geometric product of two blades with grades
+/// `lhs` and `rhs` kept by a product, or `None` if the product vanishes
+pub type GradeSelection = fn(u8, u8) -> Option<u8>;
+
+/// Grade selection of the outer product, `lhs + rhs`
+pub fn outer_product_grade(lhs: u8, rhs: u8) -> Option<u8> {
+    Some(lhs + rhs)
+}
+
+/// Grade selection of the left contraction, `rhs - lhs`
+pub fn left_contraction_grade(lhs: u8, rhs: u8) -> Option<u8> {
+    if lhs <= rhs { Some(rhs - lhs) } else { None }
+}
+
+/// Grade selection of the right contraction, `lhs - rhs`
+pub fn right_contraction_grade(lhs: u8, rhs: u8) -> Option<u8> {
+    if rhs <= lhs { Some(lhs - rhs) } else { None }
+}
+
+/// Grade selection of the scalar product, `0`
+pub fn scalar_product_grade(_lhs: u8, _rhs: u8) -> Option<u8> {
+    Some(0)
+}
+
+/// Grade selection of the Hestenes inner product, `|lhs - rhs|`, vanishing for scalars
+pub fn hestenes_inner_grade(lhs: u8, rhs: u8) -> Option<u8> {
+    if lhs == 0 || rhs == 0 { None } else { fat_dot_grade(lhs, rhs) }
+}
+
+/// Grade selection of the fat dot product, `|lhs - rhs|`
+pub fn fat_dot_grade(lhs: u8, rhs: u8) -> Option<u8> {
+    Some(if lhs <= rhs { rhs - lhs } else { lhs - rhs })
+}
+
 /// Implements a binary operator on owned types
 macro_rules! impl_operator_owned {
     (operator_type: [$($operator_type:tt)+];
@@ -261,3 +391,167 @@ macro_rules! impl_operator_outer {
         }
     }
 }
+
+macro_rules! impl_operator_geom {
+    (inline: [$($inline:tt)+];
+     generics: [$($generics:tt)*];
+     header: ($lhs:ty, $rhs:ty) -> $output:ty;
+     |&$lhs_ident:ident, &$rhs_ident:ident| $impl:expr) => {
+        impl_operator! {
+            operator_type: [GeometricProduct];
+            inline: [$($inline)+];
+            operator_fn: geom;
+            generics: [$($generics)*];
+            header: ($lhs, $rhs) -> $output;
+            |&$lhs_ident, &$rhs_ident| $impl
+        }
+
+        impl_operator! {
+            operator_type: [Mul];
+            inline: [true];
+            operator_fn: mul;
+            generics: [$($generics)*];
+            header: ($lhs, $rhs) -> $output;
+            |&lhs, &rhs| {
+                GeometricProduct::geom(lhs, rhs)
+            }
+        }
+    }
+}
+
+/// Implements all products derived from the geometric product by grade selection.
+/// The implementation receives the `GradeSelection` of the product being implemented.
+/// The contractions are also made available as the `<<` and `>>` operators.
+macro_rules! impl_operators_inner {
+    (inline: [$($inline:tt)+];
+     generics: [$($generics:tt)*];
+     header: ($lhs:ty, $rhs:ty) -> $output:ty;
+     |&$lhs_ident:ident, &$rhs_ident:ident, $selection_ident:ident| $impl:expr) => {
+        impl_operator! {
+            operator_type: [$crate::ops::LeftContraction];
+            inline: [$($inline)+];
+            operator_fn: left_contraction;
+            generics: [$($generics)*];
+            header: ($lhs, $rhs) -> $output;
+            |&$lhs_ident, &$rhs_ident| {
+                let $selection_ident: $crate::ops::GradeSelection = $crate::ops::left_contraction_grade;
+                $impl
+            }
+        }
+
+        impl_operator! {
+            operator_type: [$crate::ops::RightContraction];
+            inline: [$($inline)+];
+            operator_fn: right_contraction;
+            generics: [$($generics)*];
+            header: ($lhs, $rhs) -> $output;
+            |&$lhs_ident, &$rhs_ident| {
+                let $selection_ident: $crate::ops::GradeSelection = $crate::ops::right_contraction_grade;
+                $impl
+            }
+        }
+
+        impl_operator! {
+            operator_type: [$crate::ops::ScalarProduct];
+            inline: [$($inline)+];
+            operator_fn: scalar_product;
+            generics: [$($generics)*];
+            header: ($lhs, $rhs) -> $output;
+            |&$lhs_ident, &$rhs_ident| {
+                let $selection_ident: $crate::ops::GradeSelection = $crate::ops::scalar_product_grade;
+                $impl
+            }
+        }
+
+        impl_operator! {
+            operator_type: [$crate::ops::HestenesInner];
+            inline: [$($inline)+];
+            operator_fn: hestenes_inner;
+            generics: [$($generics)*];
+            header: ($lhs, $rhs) -> $output;
+            |&$lhs_ident, &$rhs_ident| {
+                let $selection_ident: $crate::ops::GradeSelection = $crate::ops::hestenes_inner_grade;
+                $impl
+            }
+        }
+
+        impl_operator! {
+            operator_type: [$crate::ops::FatDot];
+            inline: [$($inline)+];
+            operator_fn: fat_dot;
+            generics: [$($generics)*];
+            header: ($lhs, $rhs) -> $output;
+            |&$lhs_ident, &$rhs_ident| {
+                let $selection_ident: $crate::ops::GradeSelection = $crate::ops::fat_dot_grade;
+                $impl
+            }
+        }
+
+        impl_operator! {
+            operator_type: [::std::ops::Shl];
+            inline: [true];
+            operator_fn: shl;
+            generics: [$($generics)*];
+            header: ($lhs, $rhs) -> $output;
+            |&lhs, &rhs| {
+                $crate::ops::LeftContraction::left_contraction(lhs, rhs)
+            }
+        }
+
+        impl_operator! {
+            operator_type: [::std::ops::Shr];
+            inline: [true];
+            operator_fn: shr;
+            generics: [$($generics)*];
+            header: ($lhs, $rhs) -> $output;
+            |&lhs, &rhs| {
+                $crate::ops::RightContraction::right_contraction(lhs, rhs)
+            }
+        }
+    }
+}
+
+/// Implements all grade-based involutions.
+/// The implementation receives the `InvolutionSign` of the involution being implemented.
+macro_rules! impl_unary_operators_involution {
+    (inline: [$($inline:tt)+];
+     generics: [$($generics:tt)*];
+     header: ($input:ty) -> $output:ty;
+     |&$self:ident, $negates_ident:ident| $impl:expr) => {
+        impl_unary_operator! {
+            operator_type: [$crate::ops::Reverse];
+            inline: [$($inline)+];
+            operator_fn: reverse;
+            generics: [$($generics)*];
+            header: ($input) -> $output;
+            |&$self| {
+                let $negates_ident: $crate::ops::InvolutionSign = $crate::ops::reverse_negates;
+                $impl
+            }
+        }
+
+        impl_unary_operator! {
+            operator_type: [$crate::ops::GradeInvolution];
+            inline: [$($inline)+];
+            operator_fn: grade_involution;
+            generics: [$($generics)*];
+            header: ($input) -> $output;
+            |&$self| {
+                let $negates_ident: $crate::ops::InvolutionSign = $crate::ops::grade_involution_negates;
+                $impl
+            }
+        }
+
+        impl_unary_operator! {
+            operator_type: [$crate::ops::CliffordConjugate];
+            inline: [$($inline)+];
+            operator_fn: clifford_conjugate;
+            generics: [$($generics)*];
+            header: ($input) -> $output;
+            |&$self| {
+                let $negates_ident: $crate::ops::InvolutionSign = $crate::ops::clifford_conjugate_negates;
+                $impl
+            }
+        }
+    }
+}
@@ -0,0 +1,188 @@
+use num::Real;
+use dimension::Dimension;
+use general_multivector::GeneralMultivector;
+use ops::Reverse;
+
+/// A rotor, an even unit versor of the Euclidean space, which performs a rotation
+/// of multivectors by the sandwich product `R x ~R`
+#[derive(Clone, PartialEq, Debug)]
+pub struct Rotor<R: Real, D: Dimension> {
+    multivector: GeneralMultivector<R, D>,
+}
+
+impl<R: Real, D: Dimension> Rotor<R, D> {
+    pub fn identity() -> Self {
+        Rotor {
+            multivector: GeneralMultivector::scalar(R::one()),
+        }
+    }
+
+    /// Normalizes the multivector into a rotor, `None` if the multivector is not
+    /// an even versor, whose `R ~R` is a scalar up to the relative tolerance `epsilon`
+    pub fn from_multivector(multivector: GeneralMultivector<R, D>, epsilon: R) -> Option<Self> {
+        let odd_grades = 0xAAAA_AAAA_AAAA_AAAAu64;
+
+        if multivector.grades() & odd_grades != 0 {
+            return None;
+        }
+
+        let versor_norm = &multivector * multivector.clone().reverse();
+
+        if !is_scalar(&versor_norm, epsilon) {
+            return None;
+        }
+
+        multivector.normalized().map(|multivector| Rotor { multivector })
+    }
+
+    /// Creates a rotor rotating by `angle` in the plane of the simple bivector, from its
+    /// first basis vector towards the second, e.g. from `e1` towards `e2` for `e12`.
+    /// Computed using the closed-form exponential `exp(-angle/2 B) = cos(angle/2) - sin(angle/2) B`
+    /// of the normalized bivector `B`. Returns `None` if the bivector is zero, not of grade 2
+    /// or not simple, that is if `B^2` is not a scalar up to the relative tolerance `epsilon`.
+    pub fn from_bivector_angle(bivector: &GeneralMultivector<R, D>, angle: R, epsilon: R) -> Option<Self> {
+        if bivector.grades() != 1 << 2 || !is_scalar(&(bivector * bivector), epsilon) {
+            return None;
+        }
+
+        let unit_bivector = bivector * (R::one() / bivector.norm());
+        let half_angle = angle / (R::one() + R::one());
+
+        Some(Rotor {
+            multivector: GeneralMultivector::scalar(half_angle.cos()) - unit_bivector * half_angle.sin(),
+        })
+    }
+
+    /// The bivector `B` such that `exp(B)` is this rotor, where `B = -angle/2 * B_unit`
+    /// as in `from_bivector_angle`. Only rotors with a simple bivector part are supported,
+    /// which includes all rotors in dimensions up to 3.
+    /// Returns `None` for the rotor `-1`, whose plane of rotation is undefined.
+    pub fn log(&self) -> Option<GeneralMultivector<R, D>> {
+        let cosine = self.multivector.coefficients()[0];
+        let bivector = self.multivector.grade(2);
+        let sine = bivector.norm();
+
+        if sine.is_zero() {
+            return if cosine > R::zero() {
+                Some(GeneralMultivector::zero())
+            } else {
+                None
+            };
+        }
+
+        let half_angle = sine.atan2(cosine);
+
+        Some(bivector * (half_angle / sine))
+    }
+
+    /// Applies the rotation to the multivector, `R x ~R`
+    pub fn apply(&self, multivector: &GeneralMultivector<R, D>) -> GeneralMultivector<R, D> {
+        &self.multivector * multivector * self.multivector.clone().reverse()
+    }
+
+    /// The rotor performing the rotation of `self` followed by the rotation of `other`
+    pub fn compose(&self, other: &Self) -> Self {
+        Rotor {
+            multivector: &other.multivector * &self.multivector,
+        }
+    }
+
+    /// The rotor performing the inverse rotation, `~R`
+    pub fn inverse(&self) -> Self {
+        Rotor {
+            multivector: self.multivector.clone().reverse(),
+        }
+    }
+
+    pub fn multivector(&self) -> &GeneralMultivector<R, D> {
+        &self.multivector
+    }
+
+    pub fn into_multivector(self) -> GeneralMultivector<R, D> {
+        self.multivector
+    }
+}
+
+/// Returns `true` if the norm of the non-scalar part of the multivector is at most `epsilon`
+/// times the norm of the multivector, and the scalar part is non-zero
+fn is_scalar<R: Real, D: Dimension>(multivector: &GeneralMultivector<R, D>, epsilon: R) -> bool {
+    let scalar = multivector.coefficients()[0];
+    let non_scalar = multivector - GeneralMultivector::scalar(scalar);
+
+    !scalar.is_zero() && non_scalar.norm() <= epsilon * multivector.norm()
+}
+
+impl<R: Real, D: Dimension> Default for Rotor<R, D> {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl<R: Real, D: Dimension> From<Rotor<R, D>> for GeneralMultivector<R, D> {
+    fn from(rotor: Rotor<R, D>) -> Self {
+        rotor.multivector
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dimension::Const;
+    use std::f64::consts::PI;
+    use unit_basis_blade::UnitBasisBladeOf;
+    use scaled_basis_blade::ScaledBasisBladeOf;
+    use test_util::{assert_approx_eq, vector};
+
+    const EPSILON: f64 = 1e-12;
+
+    fn basis(bitset: u8) -> GeneralMultivector<f64, Const<3>> {
+        UnitBasisBladeOf::from(bitset).into()
+    }
+
+    #[test]
+    fn rotor_apply_and_compose() {
+        let quarter = Rotor::from_bivector_angle(&basis(0b011), PI / 2.0, EPSILON).unwrap();
+
+        assert_approx_eq(&quarter.apply(&basis(0b001)), &basis(0b010));
+        assert_approx_eq(&quarter.apply(&basis(0b100)), &basis(0b100));
+
+        let half = quarter.compose(&quarter);
+        assert_approx_eq(&half.apply(&basis(0b001)), &-basis(0b001));
+
+        // Rotating e1 to e2 about e3, followed by e2 to e3 about e1
+        let other = Rotor::from_bivector_angle(&basis(0b110), PI / 2.0, EPSILON).unwrap();
+        assert_approx_eq(&quarter.compose(&other).apply(&basis(0b001)), &basis(0b100));
+        assert_approx_eq(&quarter.compose(&quarter.inverse()).into_multivector(), &basis(0b000));
+    }
+
+    #[test]
+    fn rotor_log() {
+        let bivector: GeneralMultivector<f64, Const<3>> = GeneralMultivector::from(ScaledBasisBladeOf::new(3.0, 0b101.into()))
+            + GeneralMultivector::from(ScaledBasisBladeOf::new(4.0, 0b110.into()));
+        let rotor = Rotor::from_bivector_angle(&bivector, 1.2, EPSILON).unwrap();
+        let log = rotor.log().unwrap();
+
+        assert_approx_eq(&log, &(&bivector * (-0.6 / 5.0)));
+        assert_eq!(Rotor::<f64, Const<3>>::identity().log(), Some(GeneralMultivector::zero()));
+        assert!(Rotor::from_multivector(basis(0b001), EPSILON).is_none());
+        assert!(Rotor::from_bivector_angle(&basis(0b001), 1.0, EPSILON).is_none());
+    }
+
+    #[test]
+    fn rotor_tolerates_round_off() {
+        let a = vector::<Const<4>>(&[0.3, 0.0, 0.7, 0.0]);
+        let b = vector::<Const<4>>(&[0.0, 1.1, 0.0, 0.9]);
+        let rotor = Rotor::from_bivector_angle(&(&a ^ &b), 0.7, EPSILON).unwrap();
+        let other = Rotor::from_bivector_angle(&(vector::<Const<4>>(&[0.1, 0.2, 0.3, 0.4]) ^ vector::<Const<4>>(&[0.9, -0.3, 0.2, 0.6])), 1.3, EPSILON).unwrap();
+        let composed = rotor.compose(&other);
+
+        // Vectors orthogonal to the plane of rotation are preserved
+        let orthogonal = vector::<Const<4>>(&[0.7, 0.0, -0.3, 0.0]);
+        assert!((rotor.apply(&orthogonal) - &orthogonal).norm() < 1e-10);
+        assert!(Rotor::from_multivector(composed.into_multivector(), EPSILON).is_some());
+
+        let non_simple = GeneralMultivector::<f64, Const<4>>::from(UnitBasisBladeOf::from(0b0011u8))
+            + GeneralMultivector::from(UnitBasisBladeOf::from(0b1100u8));
+        assert!(Rotor::from_bivector_angle(&non_simple, 1.0, EPSILON).is_none());
+    }
+}
@@ -1,25 +1,35 @@
-use std::ops::BitXor;
-use dimension::{CountBits, Dimension};
-use ops::OuterProduct;
+use std::ops::{BitXor, Mul};
+use dimension::{Const, Dim, Dimension, Grade};
+use ops::{GeometricProduct, OuterProduct, Reverse, Dual, Undual, RightComplement, LeftComplement};
 use num::Real;
-use unit_basis_blade::UnitBasisBlade;
+use unit_basis_blade::UnitBasisBladeOf;
 
+/// A basis blade of a space of the dimension `D` scaled by a factor.
+/// Generic code is written against this type, concrete blades usually use the alias
+/// `ScaledBasisBlade<R, N>`.
 #[derive(Clone, Eq, PartialEq, Debug, Default)]
-pub struct ScaledBasisBlade<R: Real, D: Dimension> {
+pub struct ScaledBasisBladeOf<R: Real, D: Dimension> {
     scale: R,
-    unit_basis_blade: UnitBasisBlade<D>,
+    unit_basis_blade: UnitBasisBladeOf<D>,
 }
 
-impl<R: Real, D: Dimension> ScaledBasisBlade<R, D> {
+/// A scaled basis blade of a space with `D` basis vectors, `D` may be at most 255
+pub type ScaledBasisBlade<R, const D: usize> = ScaledBasisBladeOf<R, Const<D>>;
+
+/// A scaled basis blade of a space whose dimension is the `typenum` unsigned integer `N`,
+/// kept for the transition to `ScaledBasisBlade<R, N>`
+pub type TypenumScaledBasisBlade<R, N> = ScaledBasisBladeOf<R, Dim<N>>;
+
+impl<R: Real, D: Dimension> ScaledBasisBladeOf<R, D> {
     pub fn zero() -> Self {
-        ScaledBasisBlade {
+        ScaledBasisBladeOf {
             scale: R::zero(),
-            unit_basis_blade: UnitBasisBlade::zero(),
+            unit_basis_blade: UnitBasisBladeOf::zero(),
         }
     }
 
-    pub fn new(scale: R, unit_basis_blade: UnitBasisBlade<D>) -> Self {
-        if unit_basis_blade.is_zero() || scale.is_zero() {
+    pub fn new(scale: R, unit_basis_blade: UnitBasisBladeOf<D>) -> Self {
+        if scale.is_zero() {
             Self::zero()
         } else {
             Self {
@@ -33,82 +43,285 @@ impl<R: Real, D: Dimension> ScaledBasisBlade<R, D> {
         self.scale
     }
 
-    pub fn unit_basis_blade(&self) -> &UnitBasisBlade<D> {
+    pub fn unit_basis_blade(&self) -> &UnitBasisBladeOf<D> {
         &self.unit_basis_blade
     }
 
     pub fn is_zero(&self) -> bool {
         self.scale.is_zero()
     }
+
+    /// The squared norm `A * ~A` under the Euclidean metric
+    pub fn norm_squared(&self) -> R {
+        self.scale * self.scale
+    }
+
+    /// The norm under the Euclidean metric
+    pub fn norm(&self) -> R {
+        self.scale.abs()
+    }
+
+    /// Scales the blade to a norm of 1, `None` if the blade is zero
+    pub fn normalized(&self) -> Option<Self> {
+        if self.is_zero() {
+            None
+        } else {
+            Some(Self::new(self.scale.signum(), self.unit_basis_blade.clone()))
+        }
+    }
+
+    /// The inverse `~A / (A * ~A)` under the Euclidean metric, `None` if the blade is zero
+    pub fn inverse(&self) -> Option<Self> {
+        if self.is_zero() {
+            None
+        } else {
+            let reverse = self.clone().reverse();
+
+            Some(Self::new(reverse.scale / self.norm_squared(), reverse.unit_basis_blade))
+        }
+    }
+
+    /// The projection `(X << A) << A^-1` onto the subspace of the blade under the
+    /// Euclidean metric, `None` if the blade is zero
+    pub fn project_onto(&self, blade: &Self) -> Option<Self> {
+        blade.inverse().map(|inverse| (self << blade) << inverse)
+    }
+
+    /// The rejection `X - (X << A) << A^-1` from the subspace of the blade under the
+    /// Euclidean metric, `None` if the blade is zero
+    pub fn reject_from(&self, blade: &Self) -> Option<Self> {
+        self.project_onto(blade).map(|projection| {
+            if projection.is_zero() { self.clone() } else { Self::zero() }
+        })
+    }
+
+    /// The reflection `(-1)^(x(a + 1)) A X A^-1` in the subspace of the blade under the
+    /// Euclidean metric, `None` if the blade is zero
+    pub fn reflect_in(&self, blade: &Self) -> Option<Self> {
+        self.reflect(blade, self.grade() as u32 * (blade.grade() as u32 + 1))
+    }
+
+    /// The reflection `(-1)^(x a) A X A^-1` in the subspace dual to the blade under the
+    /// Euclidean metric, `None` if the blade is zero
+    pub fn reflect_in_dual(&self, blade: &Self) -> Option<Self> {
+        self.reflect(blade, self.grade() as u32 * blade.grade() as u32)
+    }
+
+    fn reflect(&self, blade: &Self, sign_exponent: u32) -> Option<Self> {
+        blade.inverse().map(|inverse| {
+            let reflected = blade * self * inverse;
+
+            if sign_exponent % 2 == 0 { reflected } else { Self::new(reflected.scale.neg(), reflected.unit_basis_blade) }
+        })
+    }
 }
 
-impl<R: Real, D: Dimension> From<UnitBasisBlade<D>> for ScaledBasisBlade<R, D> {
-    fn from(unit_basis_blade: UnitBasisBlade<D>) -> Self {
-        ScaledBasisBlade::new(R::one(), unit_basis_blade)
+impl<R: Real, D: Dimension> Grade for ScaledBasisBladeOf<R, D> {
+    fn grade(&self) -> u8 {
+        self.unit_basis_blade.grade()
     }
 }
 
-impl<R: Real, D: Dimension> From<(R, UnitBasisBlade<D>)> for ScaledBasisBlade<R, D> {
-    fn from(tuple: (R, UnitBasisBlade<D>)) -> Self {
+impl<R: Real, D: Dimension> From<UnitBasisBladeOf<D>> for ScaledBasisBladeOf<R, D> {
+    fn from(unit_basis_blade: UnitBasisBladeOf<D>) -> Self {
+        ScaledBasisBladeOf::new(R::one(), unit_basis_blade)
+    }
+}
+
+impl<R: Real, D: Dimension> From<(R, UnitBasisBladeOf<D>)> for ScaledBasisBladeOf<R, D> {
+    fn from(tuple: (R, UnitBasisBladeOf<D>)) -> Self {
         let (scale, unit_basis_blade) = tuple;
 
-        ScaledBasisBlade::new(scale, unit_basis_blade)
+        ScaledBasisBladeOf::new(scale, unit_basis_blade)
     }
 }
 
 impl_operator_outer! {
     inline: [false];
     generics: [R: Real, D: Dimension];
-    header: (ScaledBasisBlade<R, D>, ScaledBasisBlade<R, D>) -> ScaledBasisBlade<R, D>;
+    header: (ScaledBasisBladeOf<R, D>, ScaledBasisBladeOf<R, D>) -> ScaledBasisBladeOf<R, D>;
     |&lhs, &rhs| {
-        let mut lbs = lhs.unit_basis_blade().bitset();
-        let rbs = rhs.unit_basis_blade().bitset();
-
         // Check for linear dependency
-        if lbs & rbs != 0 {
+        if !(lhs.unit_basis_blade() & rhs.unit_basis_blade()).is_zero() {
             // If two blades are linearly dependent, the result is 0.
-            return ScaledBasisBlade::zero();
+            return ScaledBasisBladeOf::zero();
         }
 
-        let mut scale = lhs.scale() * rhs.scale();
+        let scale = lhs.scale() * rhs.scale();
 
         if scale.is_zero() {
-            return ScaledBasisBlade::zero();
+            return ScaledBasisBladeOf::zero();
         }
 
-        let resulting_bitset = lbs | rbs;
-        let mut total_swaps = 0;
+        let resulting_blade = lhs.unit_basis_blade() | rhs.unit_basis_blade();
+
+        // Negate the scale if the number of swaps was odd
+        let scale = if lhs.unit_basis_blade().canonical_reordering_is_odd(rhs.unit_basis_blade()) {
+            scale.neg()
+        } else {
+            scale
+        };
+
+        ScaledBasisBladeOf::new(scale, resulting_blade)
+    }
+}
 
-        while lbs > 1 {
-            lbs >>= 1;
-            total_swaps += (lbs & rbs).count_bits();
+impl_operator_geom! {
+    inline: [false];
+    generics: [R: Real, D: Dimension];
+    header: (ScaledBasisBladeOf<R, D>, ScaledBasisBladeOf<R, D>) -> ScaledBasisBladeOf<R, D>;
+    |&lhs, &rhs| {
+        let scale = lhs.scale() * rhs.scale();
+
+        if scale.is_zero() {
+            return ScaledBasisBladeOf::zero();
         }
 
-        // Negate the scale if the number of swaps was odd
-        scale = if total_swaps % 2 == 0 { scale } else { scale.neg() };
+        let (sign, resulting_blade) = GeometricProduct::geom(lhs.unit_basis_blade(), rhs.unit_basis_blade());
+        let scale = if sign < 0 { scale.neg() } else { scale };
+
+        ScaledBasisBladeOf::new(scale, resulting_blade)
+    }
+}
+
+impl_operators_inner! {
+    inline: [false];
+    generics: [R: Real, D: Dimension];
+    header: (ScaledBasisBladeOf<R, D>, ScaledBasisBladeOf<R, D>) -> ScaledBasisBladeOf<R, D>;
+    |&lhs, &rhs, selection| {
+        let product = lhs * rhs;
+
+        if selection(lhs.grade(), rhs.grade()) == Some(product.grade()) {
+            product
+        } else {
+            ScaledBasisBladeOf::zero()
+        }
+    }
+}
+
+impl_unary_operators_involution! {
+    inline: [true];
+    generics: [R: Real, D: Dimension];
+    header: (ScaledBasisBladeOf<R, D>) -> ScaledBasisBladeOf<R, D>;
+    |&this, negates| {
+        if negates(this.grade()) {
+            ScaledBasisBladeOf::new(this.scale().neg(), this.unit_basis_blade().clone())
+        } else {
+            this.clone()
+        }
+    }
+}
+
+/// Implements an unary operator on `ScaledBasisBladeOf` by applying the signed
+/// operator of `UnitBasisBladeOf`
+macro_rules! impl_unary_operator_signed {
+    ($operator_type:ident, $operator_fn:ident) => {
+        impl_unary_operator! {
+            operator_type: [$operator_type];
+            inline: [true];
+            operator_fn: $operator_fn;
+            generics: [R: Real, D: Dimension];
+            header: (ScaledBasisBladeOf<R, D>) -> ScaledBasisBladeOf<R, D>;
+            |&this| {
+                let (sign, unit_basis_blade) = $operator_type::$operator_fn(this.unit_basis_blade());
 
-        ScaledBasisBlade::new(scale, UnitBasisBlade::new(resulting_bitset))
+                ScaledBasisBladeOf::new(if sign < 0 { this.scale().neg() } else { this.scale() }, unit_basis_blade)
+            }
+        }
     }
 }
 
+impl_unary_operator_signed!(Dual, dual);
+impl_unary_operator_signed!(Undual, undual);
+impl_unary_operator_signed!(RightComplement, right_complement);
+impl_unary_operator_signed!(LeftComplement, left_complement);
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use typenum::U3;
+    use ops::{ScalarProduct, HestenesInner, FatDot};
 
     #[test]
     fn outer_product_1() {
-        let a: ScaledBasisBlade<f32, U3> = (2.0, 0b110.into()).into();
-        let b: ScaledBasisBlade<f32, U3> = (3.0, 0b001.into()).into();
+        let a: ScaledBasisBlade<f32, 3> = (2.0, 0b110.into()).into();
+        let b: ScaledBasisBlade<f32, 3> = (3.0, 0b001.into()).into();
 
-        assert_eq!(a^b, ScaledBasisBlade::new(6.0, 0b111.into()));
+        assert_eq!(a^b, ScaledBasisBladeOf::new(6.0, 0b111.into()));
     }
 
     #[test]
     fn outer_product_2() {
-        let a: ScaledBasisBlade<f32, U3> = (5.0, 0b110.into()).into();
-        let b: ScaledBasisBlade<f32, U3> = (7.0, 0b110.into()).into();
+        let a: ScaledBasisBlade<f32, 3> = (5.0, 0b110.into()).into();
+        let b: ScaledBasisBlade<f32, 3> = (7.0, 0b110.into()).into();
+
+        assert_eq!(a^b, ScaledBasisBladeOf::zero());
+    }
+
+    #[test]
+    fn outer_product_scalar() {
+        let a: ScaledBasisBlade<f32, 3> = (2.0, 0b000.into()).into();
+        let b: ScaledBasisBlade<f32, 3> = (3.0, 0b101.into()).into();
+
+        assert_eq!(a^b, ScaledBasisBladeOf::new(6.0, 0b101.into()));
+    }
+
+    #[test]
+    fn geometric_product_1() {
+        let e1: ScaledBasisBlade<f32, 3> = UnitBasisBladeOf::from(0b001u8).into();
+        let e2: ScaledBasisBlade<f32, 3> = UnitBasisBladeOf::from(0b010u8).into();
+
+        assert_eq!(&e1 * &e1, ScaledBasisBladeOf::new(1.0, 0b000.into()));
+        assert_eq!(&e1 * &e2, ScaledBasisBladeOf::new(1.0, 0b011.into()));
+        assert_eq!(&e2 * &e1, ScaledBasisBladeOf::new(-1.0, 0b011.into()));
+    }
+
+    #[test]
+    fn geometric_product_2() {
+        let a: ScaledBasisBlade<f32, 3> = (2.0, 0b011.into()).into();
+        let b: ScaledBasisBlade<f32, 3> = (3.0, 0b110.into()).into();
+
+        // e12 * e23 = e1 e2 e2 e3 = e13
+        assert_eq!(a * b, ScaledBasisBladeOf::new(6.0, 0b101.into()));
+    }
+
+    #[test]
+    fn inverse() {
+        let a: ScaledBasisBlade<f32, 3> = (2.0, 0b011.into()).into();
+
+        assert_eq!(a.norm(), 2.0);
+        assert_eq!(a.normalized(), Some(ScaledBasisBladeOf::new(1.0, 0b011.into())));
+        assert_eq!(a.inverse(), Some(ScaledBasisBladeOf::new(-0.5, 0b011.into())));
+        assert_eq!(&a * a.inverse().unwrap(), ScaledBasisBladeOf::new(1.0, 0b000.into()));
+        assert_eq!(ScaledBasisBlade::<f32, 3>::zero().inverse(), None);
+    }
+
+    #[test]
+    fn projection_and_reflection() {
+        let e1: ScaledBasisBlade<f32, 3> = (2.0, 0b001.into()).into();
+        let e3: ScaledBasisBlade<f32, 3> = (3.0, 0b100.into()).into();
+        let e12: ScaledBasisBlade<f32, 3> = (5.0, 0b011.into()).into();
+
+        assert_eq!(e1.project_onto(&e12), Some(e1.clone()));
+        assert_eq!(e3.project_onto(&e12), Some(ScaledBasisBladeOf::zero()));
+        assert_eq!(e3.reject_from(&e12), Some(e3.clone()));
+        assert_eq!(e1.reject_from(&e12), Some(ScaledBasisBladeOf::zero()));
+        assert_eq!(e3.reflect_in(&e12), Some(ScaledBasisBladeOf::new(-3.0, 0b100.into())));
+        assert_eq!(e1.reflect_in_dual(&e3), Some(e1.clone()));
+        assert_eq!(e3.reflect_in_dual(&e3), Some(ScaledBasisBladeOf::new(-3.0, 0b100.into())));
+        assert_eq!(e1.project_onto(&ScaledBasisBladeOf::zero()), None);
+    }
+
+    #[test]
+    fn inner_products() {
+        let scalar: ScaledBasisBlade<f32, 3> = (2.0, 0b000.into()).into();
+        let e1: ScaledBasisBlade<f32, 3> = (3.0, 0b001.into()).into();
+        let e12: ScaledBasisBlade<f32, 3> = (5.0, 0b011.into()).into();
 
-        assert_eq!(a^b, ScaledBasisBlade::new(35.0, 0b000.into()));
+        assert_eq!(&e1 << &e12, ScaledBasisBladeOf::new(15.0, 0b010.into()));
+        assert_eq!(&e12 >> &e1, ScaledBasisBladeOf::new(-15.0, 0b010.into()));
+        assert_eq!(&e12 << &e1, ScaledBasisBladeOf::zero());
+        assert_eq!((&e12).scalar_product(&e12), ScaledBasisBladeOf::new(-25.0, 0b000.into()));
+        assert_eq!((&scalar).hestenes_inner(&e1), ScaledBasisBladeOf::zero());
+        assert_eq!((&scalar).fat_dot(&e1), ScaledBasisBladeOf::new(6.0, 0b001.into()));
     }
 }
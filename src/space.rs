@@ -1,17 +1,721 @@
+use std::marker::PhantomData;
+use std::ops::Add;
+use std::borrow::Cow;
+use std::sync::Arc;
+use typenum::{Unsigned, Sum};
+use num_traits::{Zero, One};
+use alga::general::Real as GeneralReal;
+use nalgebra::{DMatrix, DVector};
 use num::Real;
-use dimension::Dimension;
+use dimension::{Bitset, Dim, Dimension, Grade, IntoDimension};
+use ops::{Reverse, GradeInvolution, CliffordConjugate, GradeSelection, scalar_product_grade,
+          hestenes_inner_grade, fat_dot_grade};
+use unit_basis_blade::UnitBasisBladeOf;
+use scaled_basis_blade::ScaledBasisBladeOf;
+use general_multivector::GeneralMultivector;
+use cayley_table::{CayleyTable, ProductKind, cayley_table};
 
-// struct MetricProperties<R: Real> {
-//     // Making this statically allocated requires usage of
-//     // `<Self as T>::R` bounds on the `Dimension` trait, which
-//     // is out of the question, as they'd have to be declared
-//     // in any function using `Dimension`/`Space` as a generic bound.
-//     metric_tensor: DMatrix<R>,
-// }
+/// Properties of an arbitrary (not necessarily diagonal) metric.
+/// The symmetric metric tensor is diagonalized, so that products may be computed
+/// in an orthogonal eigenbasis, in which every basis vector squares to an eigenvalue
+/// of the metric tensor.
+#[derive(Clone, PartialEq, Debug)]
+pub struct MetricProperties<R: Real> {
+    // Making this statically allocated requires usage of
+    // `<Self as T>::R` bounds on the `Dimension` trait, which
+    // is out of the question, as they'd have to be declared
+    // in any function using `Dimension`/`Space` as a generic bound.
+    metric_tensor: DMatrix<R>,
+    eigenvalues: DVector<R>,
+    /// Columns are the eigenbasis vectors expressed in the original basis
+    from_eigenbasis: DMatrix<R>,
+    /// The inverse (transpose) of `from_eigenbasis`
+    to_eigenbasis: DMatrix<R>,
+}
+
+impl<R: Real> MetricProperties<R> {
+    /// Diagonalizes the metric tensor, panics if it is not a symmetric matrix.
+    pub fn new(metric_tensor: DMatrix<R>) -> Self {
+        assert!(metric_tensor.is_square(), "The metric tensor must be a square matrix.");
+        assert!(metric_tensor == metric_tensor.transpose(), "The metric tensor must be symmetric.");
+
+        let eigen = metric_tensor.clone().symmetric_eigen();
+
+        MetricProperties {
+            metric_tensor,
+            eigenvalues: eigen.eigenvalues,
+            to_eigenbasis: eigen.eigenvectors.transpose(),
+            from_eigenbasis: eigen.eigenvectors,
+        }
+    }
+
+    pub fn metric_tensor(&self) -> &DMatrix<R> {
+        &self.metric_tensor
+    }
+
+    /// The squares of the eigenbasis vectors
+    pub fn eigenvalues(&self) -> &DVector<R> {
+        &self.eigenvalues
+    }
+
+    /// Expresses a multivector given in the original basis in the eigenbasis
+    pub fn to_eigenbasis<D: Dimension>(&self, multivector: &GeneralMultivector<R, D>) -> GeneralMultivector<R, D> {
+        apply_outermorphism(&self.to_eigenbasis, multivector)
+    }
+
+    /// Expresses a multivector given in the eigenbasis in the original basis
+    pub fn from_eigenbasis<D: Dimension>(&self, multivector: &GeneralMultivector<R, D>) -> GeneralMultivector<R, D> {
+        apply_outermorphism(&self.from_eigenbasis, multivector)
+    }
+}
+
+/// Applies the linear map given by `matrix` (acting on vector coordinates) to every
+/// vector factor of every blade of the multivector.
+fn apply_outermorphism<R: Real, D: Dimension>(matrix: &DMatrix<R>, multivector: &GeneralMultivector<R, D>)
+        -> GeneralMultivector<R, D> {
+    let dimension = D::to_usize();
+
+    assert_eq!((matrix.nrows(), matrix.ncols()), (dimension, dimension),
+               "The metric tensor does not match the dimension of the space.");
+
+    let images: Vec<GeneralMultivector<R, D>> = (0..dimension).map(|column| {
+        let mut image = GeneralMultivector::zero();
+
+        for row in 0..dimension {
+            image.set_coefficient(&UnitBasisBladeOf::basis_vector(row as u8), matrix[(row, column)]);
+        }
+
+        image
+    }).collect();
+
+    let mut result = GeneralMultivector::zero();
+
+    for blade in multivector.blades() {
+        let mut image = GeneralMultivector::scalar(blade.scale());
+
+        for index in 0..dimension {
+            if blade.unit_basis_blade().contains_basis_vector(index as u8) {
+                image = image ^ &images[index];
+            }
+        }
+
+        result = result + image;
+    }
 
-trait Space {
+    result
+}
+
+/// The product of the squares of all basis vectors contained in the bitset of a basis blade
+/// of an orthogonal basis
+pub fn metric_factor<R: Real, B: Bitset, F: Fn(u8) -> R>(bitset: B, basis_vector_square: F) -> R {
+    let mut factor = R::one();
+    let mut remaining = bitset;
+    let mut index = 0;
+
+    while remaining != B::zero() {
+        if remaining & B::one() != B::zero() {
+            factor *= basis_vector_square(index);
+        }
+
+        remaining = remaining >> 1;
+        index += 1;
+    }
+
+    factor
+}
+
+/// Computes the geometric product of two basis blades of an orthogonal basis, where
+/// `metric_factor` returns the product of the squares of the basis vectors of a blade
+pub fn geom_blades_with<R, D, F>(lhs: &ScaledBasisBladeOf<R, D>, rhs: &ScaledBasisBladeOf<R, D>, metric_factor: F)
+        -> ScaledBasisBladeOf<R, D>
+        where R: Real,
+              D: Dimension,
+              F: Fn(&UnitBasisBladeOf<D>) -> R {
+    let lhs_blade = lhs.unit_basis_blade();
+    let rhs_blade = rhs.unit_basis_blade();
+    let scale = lhs.scale() * rhs.scale() * metric_factor(&(lhs_blade & rhs_blade));
+
+    if scale.is_zero() {
+        return ScaledBasisBladeOf::zero();
+    }
+
+    // Negate the scale if the number of swaps was odd
+    let scale = if lhs_blade.canonical_reordering_is_odd(rhs_blade) { -scale } else { scale };
+
+    ScaledBasisBladeOf::new(scale, lhs_blade ^ rhs_blade)
+}
+
+/// Restricts the geometric product `product` of the blades `lhs` and `rhs` to the product
+/// derived by `selection`, if any. Zero if the grades are not accepted by the selection.
+pub fn select_blade_product<R: Real, D: Dimension>(lhs: &ScaledBasisBladeOf<R, D>, rhs: &ScaledBasisBladeOf<R, D>,
+                                                   product: ScaledBasisBladeOf<R, D>,
+                                                   selection: Option<GradeSelection>) -> ScaledBasisBladeOf<R, D> {
+    match selection {
+        Some(selection) if selection(lhs.grade(), rhs.grade()) != Some(product.grade()) => ScaledBasisBladeOf::zero(),
+        _ => product,
+    }
+}
+
+/// The square of the basis vector with the given index in a space with the signature `(p, q, r)`,
+/// where the first `p` basis vectors square to 1, the next `q` to -1 and the last `r` to 0
+pub fn signature_basis_vector_square<R: Real>(p: u8, q: u8, index: u8) -> R {
+    if index < p {
+        R::one()
+    } else if index < p + q {
+        -R::one()
+    } else {
+        R::zero()
+    }
+}
+
+/// A vector space equipped with a metric, which determines the geometric product.
+/// The metric is a property of the type, so that products may be inlined without
+/// any runtime lookup.
+pub trait Space: Sized + 'static {
     type Dimension: Dimension;
     type Scalar: Real;
 
-//     fn metric_properties() -> &'static MetricProperties<Self::Scalar>;
+    /// The square `e_i * e_i` of the basis vector with index `i` of the orthogonal basis
+    /// in which products are computed, indices start from 0
+    fn basis_vector_square(index: u8) -> Self::Scalar;
+
+    /// Expresses a multivector in the orthogonal basis in which products are computed.
+    /// Spaces with a diagonal metric are already expressed in such a basis.
+    fn to_orthogonal_basis<'a>(multivector: &'a GeneralMultivector<Self::Scalar, Self::Dimension>)
+            -> Cow<'a, GeneralMultivector<Self::Scalar, Self::Dimension>> {
+        Cow::Borrowed(multivector)
+    }
+
+    /// The inverse of `to_orthogonal_basis`
+    fn from_orthogonal_basis(multivector: GeneralMultivector<Self::Scalar, Self::Dimension>)
+            -> GeneralMultivector<Self::Scalar, Self::Dimension> {
+        multivector
+    }
+
+    /// The product of the squares of all basis vectors contained in the blade of the orthogonal basis
+    fn metric_factor(unit_basis_blade: &UnitBasisBladeOf<Self::Dimension>) -> Self::Scalar {
+        metric_factor(unit_basis_blade.bitset(), Self::basis_vector_square)
+    }
+
+    /// Computes the geometric product of two basis blades of the orthogonal basis
+    /// under the metric of this space
+    fn geom_blades(lhs: &ScaledBasisBladeOf<Self::Scalar, Self::Dimension>,
+                   rhs: &ScaledBasisBladeOf<Self::Scalar, Self::Dimension>)
+                   -> ScaledBasisBladeOf<Self::Scalar, Self::Dimension> {
+        geom_blades_with(lhs, rhs, Self::metric_factor)
+    }
+
+    /// Returns the Cayley table of the product in the orthogonal basis of this space,
+    /// see `cayley_table::cayley_table`
+    fn cayley_table(kind: ProductKind) -> Option<Arc<CayleyTable<Self::Scalar>>> {
+        cayley_table::<Self>(kind)
+    }
+
+    /// Computes the product of two multivectors under the metric of this space,
+    /// using the Cayley table of the product if available
+    fn product_of_kind(lhs: &GeneralMultivector<Self::Scalar, Self::Dimension>,
+                       rhs: &GeneralMultivector<Self::Scalar, Self::Dimension>,
+                       kind: ProductKind)
+                       -> GeneralMultivector<Self::Scalar, Self::Dimension> {
+        let lhs = Self::to_orthogonal_basis(lhs);
+        let rhs = Self::to_orthogonal_basis(rhs);
+        let result = match Self::cayley_table(kind) {
+            Some(table) => table.product(&lhs, &rhs),
+            None => product_by_blades::<Self>(&lhs, &rhs, kind.selection()),
+        };
+
+        Self::from_orthogonal_basis(result)
+    }
+
+    /// Computes the geometric product of two multivectors under the metric of this space
+    fn geom(lhs: &GeneralMultivector<Self::Scalar, Self::Dimension>,
+            rhs: &GeneralMultivector<Self::Scalar, Self::Dimension>)
+            -> GeneralMultivector<Self::Scalar, Self::Dimension> {
+        Self::product_of_kind(lhs, rhs, ProductKind::Geometric)
+    }
+
+    /// Computes the outer product of two multivectors, which does not depend on the metric
+    fn outer(lhs: &GeneralMultivector<Self::Scalar, Self::Dimension>,
+             rhs: &GeneralMultivector<Self::Scalar, Self::Dimension>)
+             -> GeneralMultivector<Self::Scalar, Self::Dimension> {
+        Self::product_of_kind(lhs, rhs, ProductKind::Outer)
+    }
+
+    /// Computes a product derived from the geometric product by grade selection
+    /// under the metric of this space
+    fn product(lhs: &GeneralMultivector<Self::Scalar, Self::Dimension>,
+               rhs: &GeneralMultivector<Self::Scalar, Self::Dimension>,
+               selection: GradeSelection)
+               -> GeneralMultivector<Self::Scalar, Self::Dimension> {
+        let lhs = Self::to_orthogonal_basis(lhs);
+        let rhs = Self::to_orthogonal_basis(rhs);
+        let result = match Self::cayley_table(ProductKind::Geometric) {
+            Some(table) => table.product_with_selection(&lhs, &rhs, selection),
+            None => product_by_blades::<Self>(&lhs, &rhs, Some(selection)),
+        };
+
+        Self::from_orthogonal_basis(result)
+    }
+
+    fn left_contraction(lhs: &GeneralMultivector<Self::Scalar, Self::Dimension>,
+                        rhs: &GeneralMultivector<Self::Scalar, Self::Dimension>)
+                        -> GeneralMultivector<Self::Scalar, Self::Dimension> {
+        Self::product_of_kind(lhs, rhs, ProductKind::LeftContraction)
+    }
+
+    fn right_contraction(lhs: &GeneralMultivector<Self::Scalar, Self::Dimension>,
+                         rhs: &GeneralMultivector<Self::Scalar, Self::Dimension>)
+                         -> GeneralMultivector<Self::Scalar, Self::Dimension> {
+        Self::product_of_kind(lhs, rhs, ProductKind::RightContraction)
+    }
+
+    fn scalar_product(lhs: &GeneralMultivector<Self::Scalar, Self::Dimension>,
+                      rhs: &GeneralMultivector<Self::Scalar, Self::Dimension>)
+                      -> Self::Scalar {
+        Self::product(lhs, rhs, scalar_product_grade).coefficients()[0]
+    }
+
+    fn hestenes_inner(lhs: &GeneralMultivector<Self::Scalar, Self::Dimension>,
+                      rhs: &GeneralMultivector<Self::Scalar, Self::Dimension>)
+                      -> GeneralMultivector<Self::Scalar, Self::Dimension> {
+        Self::product(lhs, rhs, hestenes_inner_grade)
+    }
+
+    fn fat_dot(lhs: &GeneralMultivector<Self::Scalar, Self::Dimension>,
+               rhs: &GeneralMultivector<Self::Scalar, Self::Dimension>)
+               -> GeneralMultivector<Self::Scalar, Self::Dimension> {
+        Self::product(lhs, rhs, fat_dot_grade)
+    }
+
+    /// The dual `A << I^-1` under the metric of this space, `None` if the pseudoscalar
+    /// is not invertible, as is the case for degenerate metrics. The metric-independent
+    /// `RightComplement` may be used instead in such a case.
+    fn dual(multivector: &GeneralMultivector<Self::Scalar, Self::Dimension>)
+            -> Option<GeneralMultivector<Self::Scalar, Self::Dimension>> {
+        let pseudoscalar: GeneralMultivector<Self::Scalar, Self::Dimension> = UnitBasisBladeOf::pseudoscalar().into();
+        let reverse = pseudoscalar.clone().reverse();
+        let square = Self::scalar_product(&pseudoscalar, &reverse);
+
+        if square.is_zero() {
+            None
+        } else {
+            Some(Self::left_contraction(multivector, &(reverse * (Self::Scalar::one() / square))))
+        }
+    }
+
+    /// The undual `A << I` under the metric of this space, the inverse of `dual`
+    fn undual(multivector: &GeneralMultivector<Self::Scalar, Self::Dimension>)
+              -> GeneralMultivector<Self::Scalar, Self::Dimension> {
+        Self::left_contraction(multivector, &UnitBasisBladeOf::pseudoscalar().into())
+    }
+
+    /// The squared norm `<A ~A>_0`, which may be negative or zero for non-zero
+    /// multivectors in mixed or degenerate signatures
+    fn norm_squared(multivector: &GeneralMultivector<Self::Scalar, Self::Dimension>) -> Self::Scalar {
+        Self::scalar_product(multivector, &multivector.clone().reverse())
+    }
+
+    /// The norm `sqrt(|<A ~A>_0|)`
+    fn norm(multivector: &GeneralMultivector<Self::Scalar, Self::Dimension>) -> Self::Scalar {
+        let norm_squared = Self::norm_squared(multivector);
+
+        norm_squared.abs().sqrt()
+    }
+
+    /// Scales the multivector to a norm of 1, `None` if the norm is zero
+    fn normalized(multivector: &GeneralMultivector<Self::Scalar, Self::Dimension>)
+                  -> Option<GeneralMultivector<Self::Scalar, Self::Dimension>> {
+        let norm = Self::norm(multivector);
+
+        if norm.is_zero() {
+            None
+        } else {
+            Some(multivector * (Self::Scalar::one() / norm))
+        }
+    }
+
+    /// The inverse `A^-1` satisfying `A A^-1 = A^-1 A = 1`, `None` if the multivector
+    /// is not invertible.
+    ///
+    /// Versors are inverted as `~A / (A ~A)`. Other multivectors are inverted using
+    /// the closed-form formulas by Hitzer and Sangwine for dimensions up to 5,
+    /// which are composed of the Clifford conjugate and the involutions. In higher
+    /// dimensions, the linear system `A X = 1` is solved.
+    fn inverse(multivector: &GeneralMultivector<Self::Scalar, Self::Dimension>)
+               -> Option<GeneralMultivector<Self::Scalar, Self::Dimension>> {
+        let reverse = multivector.clone().reverse();
+        let versor_norm = Self::geom(multivector, &reverse);
+
+        if versor_norm.grades() <= 1 {
+            return divide_by_scalar(reverse, versor_norm.coefficients()[0]);
+        }
+
+        let conjugate = multivector.clone().clifford_conjugate();
+        let numerator = match Self::Dimension::to_usize() {
+            0..=2 => conjugate,
+            3 => {
+                Self::geom(&Self::geom(&conjugate, &multivector.clone().grade_involution()), &reverse)
+            },
+            4 => {
+                let product = Self::geom(multivector, &conjugate);
+
+                Self::geom(&conjugate, &negate_grades(&product, &[3, 4]))
+            },
+            5 => {
+                let partial = Self::geom(&Self::geom(&conjugate, &multivector.clone().grade_involution()), &reverse);
+                let product = Self::geom(multivector, &partial);
+
+                Self::geom(&partial, &negate_grades(&product, &[1, 4]))
+            },
+            _ => return Self::inverse_by_linear_system(multivector),
+        };
+        let denominator = Self::geom(multivector, &numerator);
+
+        divide_by_scalar(numerator, denominator.coefficients()[0])
+    }
+
+    /// The orthogonal projection `(X << A) << A^-1` of `X` onto the subspace of the blade `A`,
+    /// `None` if the blade is not invertible
+    fn project_onto(multivector: &GeneralMultivector<Self::Scalar, Self::Dimension>,
+                    blade: &GeneralMultivector<Self::Scalar, Self::Dimension>)
+                    -> Option<GeneralMultivector<Self::Scalar, Self::Dimension>> {
+        let inverse = Self::inverse(blade)?;
+
+        Some(Self::left_contraction(&Self::left_contraction(multivector, blade), &inverse))
+    }
+
+    /// The rejection `X - (X << A) << A^-1` of `X` from the subspace of the blade `A`,
+    /// which for a vector is the component orthogonal to the subspace,
+    /// `None` if the blade is not invertible
+    fn reject_from(multivector: &GeneralMultivector<Self::Scalar, Self::Dimension>,
+                   blade: &GeneralMultivector<Self::Scalar, Self::Dimension>)
+                   -> Option<GeneralMultivector<Self::Scalar, Self::Dimension>> {
+        Self::project_onto(multivector, blade).map(|projection| multivector - projection)
+    }
+
+    /// The reflection `(-1)^(x(a + 1)) A X A^-1` of `X` in the subspace of the blade `A`,
+    /// applied to every grade `x` of `X`, where `a` is the grade of `A`.
+    /// `None` if the blade is not homogeneous or not invertible.
+    fn reflect_in(multivector: &GeneralMultivector<Self::Scalar, Self::Dimension>,
+                  blade: &GeneralMultivector<Self::Scalar, Self::Dimension>)
+                  -> Option<GeneralMultivector<Self::Scalar, Self::Dimension>> {
+        reflect::<Self>(multivector, blade, |grade, blade_grade| grade * (blade_grade + 1))
+    }
+
+    /// The reflection `(-1)^(x a) A X A^-1` of `X` in the subspace dual to the blade `A`,
+    /// e.g. in the hyperplane with the normal vector `A`, applied to every grade `x` of `X`,
+    /// where `a` is the grade of `A`.
+    /// `None` if the blade is not homogeneous or not invertible.
+    fn reflect_in_dual(multivector: &GeneralMultivector<Self::Scalar, Self::Dimension>,
+                       blade: &GeneralMultivector<Self::Scalar, Self::Dimension>)
+                       -> Option<GeneralMultivector<Self::Scalar, Self::Dimension>> {
+        reflect::<Self>(multivector, blade, |grade, blade_grade| grade * blade_grade)
+    }
+
+    /// Computes the inverse by solving the linear system `A X = 1`
+    fn inverse_by_linear_system(multivector: &GeneralMultivector<Self::Scalar, Self::Dimension>)
+                                -> Option<GeneralMultivector<Self::Scalar, Self::Dimension>> {
+        let count = GeneralMultivector::<Self::Scalar, Self::Dimension>::basis_blade_count();
+        let columns: Vec<GeneralMultivector<Self::Scalar, Self::Dimension>> = (0..count)
+            .map(|index| Self::geom(multivector, &UnitBasisBladeOf::new(Bitset::from_usize(index)).into()))
+            .collect();
+        let matrix = DMatrix::from_fn(count, count, |row, column| columns[column].coefficients()[row]);
+        let mut one = DVector::from_element(count, Self::Scalar::zero());
+        one[0] = Self::Scalar::one();
+
+        matrix.lu().solve(&one).map(|solution| {
+            GeneralMultivector::from_coefficients(solution.iter().cloned().collect())
+        })
+    }
+}
+
+/// Computes the sandwich product `A X A^-1` grade by grade, negating the grades `x`
+/// of `X` for which `sign_exponent(x, a)` is odd
+fn reflect<S: Space + ?Sized>(multivector: &GeneralMultivector<S::Scalar, S::Dimension>,
+                              blade: &GeneralMultivector<S::Scalar, S::Dimension>,
+                              sign_exponent: fn(u32, u32) -> u32)
+                              -> Option<GeneralMultivector<S::Scalar, S::Dimension>> {
+    if !blade.is_homogeneous() {
+        return None;
+    }
+
+    let blade_grade = blade.grades().trailing_zeros();
+    let inverse = S::inverse(blade)?;
+    let mut result = GeneralMultivector::zero();
+
+    for grade in 0..(S::Dimension::to_u8() + 1) {
+        let part = multivector.grade(grade);
+
+        if part.is_zero() {
+            continue;
+        }
+
+        let reflected = S::geom(&S::geom(blade, &part), &inverse);
+
+        result = if sign_exponent(grade as u32, blade_grade) % 2 == 0 {
+            result + reflected
+        } else {
+            result - reflected
+        };
+    }
+
+    Some(result)
+}
+
+fn divide_by_scalar<R: Real, D: Dimension>(multivector: GeneralMultivector<R, D>, scalar: R)
+        -> Option<GeneralMultivector<R, D>> {
+    if scalar.is_zero() {
+        None
+    } else {
+        Some(multivector * (R::one() / scalar))
+    }
+}
+
+/// Negates the parts of the multivector of the given grades
+fn negate_grades<R: Real, D: Dimension>(multivector: &GeneralMultivector<R, D>, grades: &[u8])
+        -> GeneralMultivector<R, D> {
+    let mut result = multivector.clone();
+
+    for &grade in grades {
+        for blade in multivector.blades_of_grade(grade) {
+            result.set_coefficient(blade.unit_basis_blade(), -blade.scale());
+        }
+    }
+
+    result
+}
+
+/// Computes a product of multivectors expressed in the orthogonal basis of the space blade
+/// by blade, including only the products whose grades are accepted by `selection`, if any
+fn product_by_blades<S: Space>(lhs: &GeneralMultivector<S::Scalar, S::Dimension>,
+                               rhs: &GeneralMultivector<S::Scalar, S::Dimension>,
+                               selection: Option<GradeSelection>)
+                               -> GeneralMultivector<S::Scalar, S::Dimension> {
+    let mut result = GeneralMultivector::zero();
+
+    for lhs_blade in lhs.blades() {
+        for rhs_blade in rhs.blades() {
+            let product = S::geom_blades(&lhs_blade, &rhs_blade);
+
+            result.add_blade(&select_blade_product(&lhs_blade, &rhs_blade, product, selection));
+        }
+    }
+
+    result
+}
+
+/// The Euclidean space of dimension `D`, where every basis vector squares to 1.
+/// This is the metric used by the operator implementations on blades and multivectors.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Euclidean<R: Real, D: Dimension> {
+    _marker: PhantomData<(R, D)>,
+}
+
+impl<R: Real, D: Dimension> Space for Euclidean<R, D> {
+    type Dimension = D;
+    type Scalar = R;
+
+    #[inline]
+    fn basis_vector_square(_index: u8) -> R {
+        R::one()
+    }
+}
+
+/// A space with a diagonal metric of signature `(P, Q, Z)`.
+/// The first `P` basis vectors square to 1, the following `Q` basis vectors square
+/// to -1 and the last `Z` basis vectors square to 0. The dimension of the space
+/// is `P + Q + Z`. The counts are `typenum` unsigned integers, so that the dimension
+/// can be computed at the type level, and converted into the dimension `Const<P + Q + Z>`.
+///
+/// For example, `Signature<R, U1, U3, U0>` is the Minkowski spacetime and
+/// `Signature<R, U3, U0, U1>` is the space of 3D projective geometric algebra.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Signature<R: Real, P: Unsigned, Q: Unsigned, Z: Unsigned> {
+    _marker: PhantomData<(R, P, Q, Z)>,
+}
+
+impl<R: Real, P: Unsigned + 'static, Q: Unsigned + 'static, Z: Unsigned + 'static> Space for Signature<R, P, Q, Z>
+        where P: Add<Q>,
+              Sum<P, Q>: Add<Z>,
+              Sum<Sum<P, Q>, Z>: IntoDimension {
+    type Dimension = Dim<Sum<Sum<P, Q>, Z>>;
+    type Scalar = R;
+
+    #[inline]
+    fn basis_vector_square(index: u8) -> R {
+        signature_basis_vector_square(P::to_u8(), Q::to_u8(), index)
+    }
+}
+
+/// Provides the metric of a space whose basis is not necessarily orthogonal
+pub trait Metric: 'static {
+    type Dimension: Dimension;
+    type Scalar: Real;
+
+    fn metric_properties() -> &'static MetricProperties<Self::Scalar>;
+}
+
+/// A space with an arbitrary symmetric metric tensor, provided by `M`.
+/// Products are computed in the eigenbasis of the metric tensor, the results are
+/// expressed in the original basis.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MetricSpace<M: Metric> {
+    _marker: PhantomData<M>,
+}
+
+impl<M: Metric> Space for MetricSpace<M> {
+    type Dimension = M::Dimension;
+    type Scalar = M::Scalar;
+
+    #[inline]
+    fn basis_vector_square(index: u8) -> Self::Scalar {
+        M::metric_properties().eigenvalues()[index as usize]
+    }
+
+    fn to_orthogonal_basis<'a>(multivector: &'a GeneralMultivector<Self::Scalar, Self::Dimension>)
+            -> Cow<'a, GeneralMultivector<Self::Scalar, Self::Dimension>> {
+        Cow::Owned(M::metric_properties().to_eigenbasis(multivector))
+    }
+
+    fn from_orthogonal_basis(multivector: GeneralMultivector<Self::Scalar, Self::Dimension>)
+            -> GeneralMultivector<Self::Scalar, Self::Dimension> {
+        M::metric_properties().from_eigenbasis(&multivector)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dimension::Const;
+    use scaled_basis_blade::ScaledBasisBlade;
+    use typenum::{U0, U1, U3};
+    use test_util::assert_approx_eq;
+
+    type Minkowski = Signature<f64, U1, U3, U0>;
+    type Projective = Signature<f64, U3, U0, U1>;
+
+    #[test]
+    fn signature_basis_vector_squares() {
+        let squares: Vec<f64> = (0..4).map(Minkowski::basis_vector_square).collect();
+
+        assert_eq!(squares, vec![1.0, -1.0, -1.0, -1.0]);
+        assert_eq!(Projective::basis_vector_square(3), 0.0);
+    }
+
+    #[test]
+    fn signature_geometric_product() {
+        let e0: ScaledBasisBlade<f64, 4> = UnitBasisBladeOf::from(0b0001u8).into();
+        let e1: ScaledBasisBlade<f64, 4> = UnitBasisBladeOf::from(0b0010u8).into();
+        let e3: ScaledBasisBlade<f64, 4> = UnitBasisBladeOf::from(0b1000u8).into();
+
+        assert_eq!(Minkowski::geom_blades(&e0, &e0), ScaledBasisBladeOf::new(1.0, 0b0000.into()));
+        assert_eq!(Minkowski::geom_blades(&e1, &e1), ScaledBasisBladeOf::new(-1.0, 0b0000.into()));
+        assert_eq!(Minkowski::geom_blades(&e1, &e0), ScaledBasisBladeOf::new(-1.0, 0b0011.into()));
+        assert!(Projective::geom_blades(&e3, &e3).is_zero());
+
+        // (e1 e3)^2 = -e1 e1 e3 e3 = 0 in PGA
+        let e13 = GeneralMultivector::from(&e1 * &e3);
+        assert!(Projective::geom(&e13, &e13).is_zero());
+        assert_eq!(Euclidean::<f64, Const<4>>::geom(&e13, &e13), GeneralMultivector::scalar(-1.0));
+    }
+
+    /// A 2D space with a null basis `n1`, `n2`, where `n1 . n2 = -1`
+    struct NullBasis;
+
+    impl Metric for NullBasis {
+        type Dimension = Const<2>;
+        type Scalar = f64;
+
+        fn metric_properties() -> &'static MetricProperties<f64> {
+            lazy_static! {
+                static ref PROPERTIES: MetricProperties<f64> = MetricProperties::new(
+                    DMatrix::from_row_slice(2, 2, &[0.0, -1.0, -1.0, 0.0])
+                );
+            }
+
+            &PROPERTIES
+        }
+    }
+
+    #[test]
+    fn metric_space_geometric_product() {
+        let n1: GeneralMultivector<f64, Const<2>> = UnitBasisBladeOf::from(0b01u8).into();
+        let n2: GeneralMultivector<f64, Const<2>> = UnitBasisBladeOf::from(0b10u8).into();
+
+        // Null vectors square to zero
+        assert_approx_eq(&MetricSpace::<NullBasis>::geom(&n1, &n1), &GeneralMultivector::zero());
+        assert_approx_eq(&MetricSpace::<NullBasis>::geom(&n2, &n2), &GeneralMultivector::zero());
+
+        // n1 n2 = n1 . n2 + n1 ^ n2 = -1 + n12
+        let expected = GeneralMultivector::scalar(-1.0) + (&n1 ^ &n2);
+        assert_approx_eq(&MetricSpace::<NullBasis>::geom(&n1, &n2), &expected);
+        assert!((MetricSpace::<NullBasis>::scalar_product(&n1, &n2) + 1.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn signature_contraction() {
+        let e1: GeneralMultivector<f64, Const<4>> = UnitBasisBladeOf::from(0b0010u8).into();
+        let e01: GeneralMultivector<f64, Const<4>> = UnitBasisBladeOf::from(0b0011u8).into();
+
+        // e1 << e01 = -e1 e1 e0 = e0 in Minkowski space
+        assert_eq!(Minkowski::left_contraction(&e1, &e01), UnitBasisBladeOf::from(0b0001u8).into());
+        assert!(Minkowski::right_contraction(&e1, &e01).is_zero());
+    }
+
+    #[test]
+    fn signature_dual() {
+        let e1: GeneralMultivector<f64, Const<4>> = UnitBasisBladeOf::from(0b0010u8).into();
+
+        assert_eq!(Minkowski::undual(&Minkowski::dual(&e1).unwrap()), e1);
+        assert!(Projective::dual(&e1).is_none());
+    }
+
+    fn assert_inverse<S: Space<Scalar=f64>>(multivector: &GeneralMultivector<f64, S::Dimension>) {
+        let inverse = S::inverse(multivector).unwrap();
+        let products = [S::geom(multivector, &inverse), S::geom(&inverse, multivector)];
+
+        for product in products.iter() {
+            for (index, coefficient) in product.coefficients().iter().enumerate() {
+                let expected = if index == 0 { 1.0 } else { 0.0 };
+                assert!((coefficient - expected).abs() < 1e-9, "{:?}", product.coefficients());
+            }
+        }
+    }
+
+    /// A multivector with a non-zero coefficient for every blade, which is not a versor
+    fn general_multivector<D: Dimension>() -> GeneralMultivector<f64, D> {
+        let count = GeneralMultivector::<f64, D>::basis_blade_count();
+
+        GeneralMultivector::from_coefficients((0..count).map(|index| {
+            1.0 + ((index * 7) % 5) as f64 * 0.25 - (index % 3) as f64 * 0.5
+        }).collect())
+    }
+
+    #[test]
+    fn signature_inverse() {
+        use typenum::U2;
+
+        assert_inverse::<Euclidean<f64, Const<2>>>(&general_multivector());
+        assert_inverse::<Euclidean<f64, Const<3>>>(&general_multivector());
+        assert_inverse::<Minkowski>(&general_multivector());
+        assert_inverse::<Signature<f64, U3, U2, U0>>(&general_multivector());
+        assert_inverse::<Euclidean<f64, Const<6>>>(&general_multivector());
+        assert_inverse::<Euclidean<f64, Const<5>>>(&general_multivector::<Const<5>>());
+    }
+
+    #[test]
+    fn signature_norm_and_non_invertible() {
+        // The null vector e0 + e1 in Minkowski space
+        let null: GeneralMultivector<f64, Const<4>> = GeneralMultivector::from(UnitBasisBladeOf::from(0b0001u8))
+            + GeneralMultivector::from(UnitBasisBladeOf::from(0b0010u8));
+        let e3: GeneralMultivector<f64, Const<4>> = UnitBasisBladeOf::from(0b1000u8).into();
+
+        assert_eq!(Minkowski::norm_squared(&null), 0.0);
+        assert!(Minkowski::inverse(&null).is_none());
+        assert!(Minkowski::normalized(&null).is_none());
+        assert!(Projective::inverse(&e3).is_none());
+        assert_eq!(Minkowski::norm_squared(&(&e3 * 2.0)), -4.0);
+        assert_eq!(Minkowski::norm(&(&e3 * 2.0)), 2.0);
+        assert_inverse::<Minkowski>(&(&e3 * 2.0));
+    }
 }
@@ -0,0 +1,452 @@
+use std::ops::{Add, Sub, Neg, Mul, BitXor};
+use std::collections::BTreeMap;
+use std::slice;
+use dimension::{Dimension, DimensionBitset, Grade};
+use num::Real;
+use unit_basis_blade::UnitBasisBladeOf;
+use scaled_basis_blade::ScaledBasisBladeOf;
+use general_multivector::GeneralMultivector;
+use space::select_blade_product;
+use ops::{GeometricProduct, OuterProduct, Reverse, Dual, Undual, RightComplement, LeftComplement};
+
+/// A sparse multivector, storing only the non-zero blades, sorted by bitset.
+/// Suitable for high dimensions, where storing all `2^D` coefficients is infeasible.
+///
+/// Terms with a magnitude below `epsilon` are dropped. The default epsilon of 0
+/// only drops terms which are exactly zero, so that no information is lost.
+#[derive(Clone, Debug)]
+pub struct SparseMultivector<R: Real, D: Dimension> {
+    terms: Vec<(UnitBasisBladeOf<D>, R)>,
+    epsilon: R,
+}
+
+impl<R: Real, D: Dimension> SparseMultivector<R, D> {
+    pub fn zero() -> Self {
+        SparseMultivector {
+            terms: Vec::new(),
+            epsilon: R::zero(),
+        }
+    }
+
+    pub fn scalar(scale: R) -> Self {
+        Self::from_blades(Some(ScaledBasisBladeOf::new(scale, UnitBasisBladeOf::zero())))
+    }
+
+    /// Sums the blades into a multivector
+    pub fn from_blades<I: IntoIterator<Item=ScaledBasisBladeOf<R, D>>>(blades: I) -> Self {
+        Self::zero().accumulate(blades)
+    }
+
+    /// Returns this multivector with terms below `epsilon` dropped.
+    /// The epsilon is retained for the results of operations on this multivector.
+    pub fn with_epsilon(mut self, epsilon: R) -> Self {
+        self.epsilon = epsilon;
+        self.terms.retain(|&(_, scale)| !Self::is_negligible(scale, epsilon));
+        self
+    }
+
+    pub fn epsilon(&self) -> R {
+        self.epsilon
+    }
+
+    /// The non-zero terms, sorted by bitset
+    pub fn terms(&self) -> &[(UnitBasisBladeOf<D>, R)] {
+        &self.terms
+    }
+
+    pub fn coefficient(&self, unit_basis_blade: &UnitBasisBladeOf<D>) -> R {
+        match self.terms.binary_search_by_key(&unit_basis_blade.bitset(), |&(ref blade, _)| blade.bitset()) {
+            Ok(index) => self.terms[index].1,
+            Err(_) => R::zero(),
+        }
+    }
+
+    /// Sets the coefficient of the blade, removing the term if the scale is below `epsilon`
+    pub fn set_coefficient(&mut self, unit_basis_blade: &UnitBasisBladeOf<D>, scale: R) {
+        let negligible = Self::is_negligible(scale, self.epsilon);
+
+        match self.terms.binary_search_by_key(&unit_basis_blade.bitset(), |&(ref blade, _)| blade.bitset()) {
+            Ok(index) if negligible => {
+                self.terms.remove(index);
+            },
+            Ok(index) => self.terms[index].1 = scale,
+            Err(index) if !negligible => self.terms.insert(index, (unit_basis_blade.clone(), scale)),
+            Err(_) => (),
+        }
+    }
+
+    /// Iterates over all blades with a non-zero coefficient, ordered by bitset
+    pub fn blades(&self) -> SparseBlades<R, D> {
+        SparseBlades {
+            terms: self.terms.iter(),
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// The grade projection `<A>_k`, keeping only the blades of grade `k`
+    pub fn grade(&self, k: u8) -> Self {
+        SparseMultivector {
+            terms: self.terms.iter()
+                .filter(|&&(ref blade, _)| blade.grade() == k)
+                .cloned()
+                .collect(),
+            epsilon: self.epsilon,
+        }
+    }
+
+    /// The squared norm `<A ~A>_0` under the Euclidean metric
+    pub fn norm_squared(&self) -> R {
+        self.terms.iter().fold(R::zero(), |sum, &(_, scale)| sum + scale * scale)
+    }
+
+    /// The norm under the Euclidean metric
+    pub fn norm(&self) -> R {
+        self.norm_squared().sqrt()
+    }
+
+    /// Scales the multivector to a norm of 1, `None` if the multivector is zero
+    pub fn normalized(&self) -> Option<Self> {
+        if self.is_zero() {
+            None
+        } else {
+            Some(self * (R::one() / self.norm()))
+        }
+    }
+
+    /// The inverse `~A / (A ~A)` of a versor under the Euclidean metric, `None` if
+    /// `A ~A` is not a non-zero scalar. Unlike `GeneralMultivector::inverse`, this
+    /// does not attempt to invert multivectors which are not versors, as that is
+    /// infeasible in high dimensions.
+    pub fn versor_inverse(&self) -> Option<Self> {
+        let reverse = self.clone().reverse();
+        let versor_norm = self * &reverse;
+
+        match versor_norm.terms.first() {
+            Some(&(ref blade, scale)) if versor_norm.terms.len() == 1 && blade.is_zero() => {
+                Some(reverse * (R::one() / scale))
+            },
+            _ => None,
+        }
+    }
+
+    /// Computes a bilinear product by expanding it blade by blade, e.g. using
+    /// `Space::geom_blades` for the geometric product under a diagonal metric
+    pub fn product_with<F>(&self, rhs: &Self, product: F) -> Self
+            where F: Fn(&ScaledBasisBladeOf<R, D>, &ScaledBasisBladeOf<R, D>) -> ScaledBasisBladeOf<R, D> {
+        let mut products = Vec::with_capacity(self.terms.len() * rhs.terms.len());
+
+        for lhs_blade in self.blades() {
+            for rhs_blade in rhs.blades() {
+                products.push(product(&lhs_blade, &rhs_blade));
+            }
+        }
+
+        SparseMultivector {
+            terms: Vec::new(),
+            epsilon: Self::combined_epsilon(self, rhs),
+        }.accumulate(products)
+    }
+
+    /// Applies a linear map blade by blade
+    pub fn map_blades<F>(&self, map: F) -> Self
+            where F: Fn(&ScaledBasisBladeOf<R, D>) -> ScaledBasisBladeOf<R, D> {
+        SparseMultivector {
+            terms: Vec::new(),
+            epsilon: self.epsilon,
+        }.accumulate(self.blades().map(|blade| map(&blade)))
+    }
+
+    /// Adds the blades to this multivector, merging terms of equal blades
+    fn accumulate<I: IntoIterator<Item=ScaledBasisBladeOf<R, D>>>(self, blades: I) -> Self {
+        let mut sums: BTreeMap<DimensionBitset<D>, R> = self.terms.into_iter()
+            .map(|(blade, scale)| (blade.bitset(), scale))
+            .collect();
+
+        for blade in blades {
+            if !blade.is_zero() {
+                *sums.entry(blade.unit_basis_blade().bitset()).or_insert_with(R::zero) += blade.scale();
+            }
+        }
+
+        let epsilon = self.epsilon;
+
+        SparseMultivector {
+            terms: sums.into_iter()
+                .filter(|&(_, scale)| !Self::is_negligible(scale, epsilon))
+                .map(|(bitset, scale)| (UnitBasisBladeOf::new(bitset), scale))
+                .collect(),
+            epsilon,
+        }
+    }
+
+    fn is_negligible(scale: R, epsilon: R) -> bool {
+        scale.is_zero() || (if scale < R::zero() { -scale } else { scale }) < epsilon
+    }
+
+    fn combined_epsilon(lhs: &Self, rhs: &Self) -> R {
+        if lhs.epsilon > rhs.epsilon { lhs.epsilon } else { rhs.epsilon }
+    }
+}
+
+impl<R: Real, D: Dimension> Default for SparseMultivector<R, D> {
+    fn default() -> Self {
+        Self::zero()
+    }
+}
+
+impl<R: Real, D: Dimension> PartialEq for SparseMultivector<R, D> {
+    fn eq(&self, other: &Self) -> bool {
+        self.terms.len() == other.terms.len() && self.terms.iter().zip(other.terms.iter())
+            .all(|(&(ref lhs_blade, lhs_scale), &(ref rhs_blade, rhs_scale))| {
+                lhs_blade.bitset() == rhs_blade.bitset() && lhs_scale == rhs_scale
+            })
+    }
+}
+
+impl<R: Real, D: Dimension> From<ScaledBasisBladeOf<R, D>> for SparseMultivector<R, D> {
+    fn from(blade: ScaledBasisBladeOf<R, D>) -> Self {
+        Self::from_blades(Some(blade))
+    }
+}
+
+impl<R: Real, D: Dimension> From<UnitBasisBladeOf<D>> for SparseMultivector<R, D> {
+    fn from(unit_basis_blade: UnitBasisBladeOf<D>) -> Self {
+        Self::from_blades(Some(unit_basis_blade.into()))
+    }
+}
+
+impl<R: Real, D: Dimension> From<GeneralMultivector<R, D>> for SparseMultivector<R, D> {
+    fn from(multivector: GeneralMultivector<R, D>) -> Self {
+        Self::from_blades(multivector.blades())
+    }
+}
+
+impl<R: Real, D: Dimension> From<SparseMultivector<R, D>> for GeneralMultivector<R, D> {
+    fn from(multivector: SparseMultivector<R, D>) -> Self {
+        let mut result = GeneralMultivector::zero();
+
+        for (unit_basis_blade, scale) in multivector.terms {
+            result.set_coefficient(&unit_basis_blade, scale);
+        }
+
+        result
+    }
+}
+
+/// An iterator over the non-zero blades of a `SparseMultivector`
+pub struct SparseBlades<'a, R: Real + 'a, D: Dimension + 'a> {
+    terms: slice::Iter<'a, (UnitBasisBladeOf<D>, R)>,
+}
+
+impl<'a, R: Real, D: Dimension> Iterator for SparseBlades<'a, R, D> {
+    type Item = ScaledBasisBladeOf<R, D>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.terms.next().map(|&(ref unit_basis_blade, scale)| {
+            ScaledBasisBladeOf::new(scale, unit_basis_blade.clone())
+        })
+    }
+}
+
+impl_operator! {
+    operator_type: [Add];
+    inline: [false];
+    operator_fn: add;
+    generics: [R: Real, D: Dimension];
+    header: (SparseMultivector<R, D>, SparseMultivector<R, D>) -> SparseMultivector<R, D>;
+    |&lhs, &rhs| {
+        SparseMultivector {
+            terms: lhs.terms.clone(),
+            epsilon: SparseMultivector::combined_epsilon(lhs, rhs),
+        }.accumulate(rhs.blades())
+    }
+}
+
+impl_operator! {
+    operator_type: [Sub];
+    inline: [false];
+    operator_fn: sub;
+    generics: [R: Real, D: Dimension];
+    header: (SparseMultivector<R, D>, SparseMultivector<R, D>) -> SparseMultivector<R, D>;
+    |&lhs, &rhs| {
+        SparseMultivector {
+            terms: lhs.terms.clone(),
+            epsilon: SparseMultivector::combined_epsilon(lhs, rhs),
+        }.accumulate(rhs.blades().map(|blade| ScaledBasisBladeOf::new(-blade.scale(), blade.unit_basis_blade().clone())))
+    }
+}
+
+impl_operator! {
+    operator_type: [Mul];
+    inline: [false];
+    operator_fn: mul;
+    generics: [R: Real, D: Dimension];
+    header: (SparseMultivector<R, D>, R) -> SparseMultivector<R, D>;
+    |&lhs, &rhs| {
+        lhs.map_blades(|blade| ScaledBasisBladeOf::new(blade.scale() * *rhs, blade.unit_basis_blade().clone()))
+    }
+}
+
+impl_operator_geom! {
+    inline: [false];
+    generics: [R: Real, D: Dimension];
+    header: (SparseMultivector<R, D>, SparseMultivector<R, D>) -> SparseMultivector<R, D>;
+    |&lhs, &rhs| {
+        lhs.product_with(rhs, |l, r| GeometricProduct::geom(l, r))
+    }
+}
+
+impl_operator_outer! {
+    inline: [false];
+    generics: [R: Real, D: Dimension];
+    header: (SparseMultivector<R, D>, SparseMultivector<R, D>) -> SparseMultivector<R, D>;
+    |&lhs, &rhs| {
+        lhs.product_with(rhs, |l, r| OuterProduct::outer(l, r))
+    }
+}
+
+impl_operators_inner! {
+    inline: [false];
+    generics: [R: Real, D: Dimension];
+    header: (SparseMultivector<R, D>, SparseMultivector<R, D>) -> SparseMultivector<R, D>;
+    |&lhs, &rhs, selection| {
+        lhs.product_with(rhs, |l, r| select_blade_product(l, r, l * r, Some(selection)))
+    }
+}
+
+impl_unary_operators_involution! {
+    inline: [false];
+    generics: [R: Real, D: Dimension];
+    header: (SparseMultivector<R, D>) -> SparseMultivector<R, D>;
+    |&this, negates| {
+        this.map_blades(|blade| {
+            if negates(blade.grade()) {
+                ScaledBasisBladeOf::new(-blade.scale(), blade.unit_basis_blade().clone())
+            } else {
+                blade.clone()
+            }
+        })
+    }
+}
+
+/// Implements a linear unary operator on `SparseMultivector` by applying it to every blade
+macro_rules! impl_unary_operator_sparse {
+    ($operator_type:ident, $operator_fn:ident) => {
+        impl_unary_operator! {
+            operator_type: [$operator_type];
+            inline: [false];
+            operator_fn: $operator_fn;
+            generics: [R: Real, D: Dimension];
+            header: (SparseMultivector<R, D>) -> SparseMultivector<R, D>;
+            |&this| {
+                this.map_blades(|blade| $operator_type::$operator_fn(blade))
+            }
+        }
+    }
+}
+
+impl_unary_operator_sparse!(Dual, dual);
+impl_unary_operator_sparse!(Undual, undual);
+impl_unary_operator_sparse!(RightComplement, right_complement);
+impl_unary_operator_sparse!(LeftComplement, left_complement);
+
+impl_unary_operator! {
+    operator_type: [Neg];
+    inline: [false];
+    operator_fn: neg;
+    generics: [R: Real, D: Dimension];
+    header: (SparseMultivector<R, D>) -> SparseMultivector<R, D>;
+    |&this| {
+        this.map_blades(|blade| ScaledBasisBladeOf::new(-blade.scale(), blade.unit_basis_blade().clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dimension::Const;
+    use space::{Space, Signature};
+    use typenum::{U0, U1, U3, U199};
+
+    #[test]
+    fn sparse_multivector_dense_round_trip() {
+        let dense: GeneralMultivector<f64, Const<4>> = GeneralMultivector::scalar(1.5)
+            + GeneralMultivector::from(ScaledBasisBladeOf::new(-2.0, 0b1010.into()))
+            + GeneralMultivector::from(ScaledBasisBladeOf::new(1e-12, 0b0001.into()));
+        let sparse = SparseMultivector::from(dense.clone());
+
+        assert_eq!(sparse.terms().len(), 3);
+        assert_eq!(sparse.coefficient(&0b1010.into()), -2.0);
+        assert_eq!(GeneralMultivector::from(sparse.clone()), dense);
+        assert_eq!(sparse.clone().with_epsilon(1e-9).terms().len(), 2);
+
+        let mut modified = sparse.with_epsilon(1e-9);
+        modified.set_coefficient(&0b0100.into(), 3.0);
+        modified.set_coefficient(&0b1010.into(), 1e-12);
+        assert_eq!(modified.terms().len(), 2);
+        assert_eq!(modified.coefficient(&0b0100.into()), 3.0);
+        assert_eq!(modified.coefficient(&0b1010.into()), 0.0);
+    }
+
+    #[test]
+    fn sparse_multivector_products_match_dense() {
+        let a: GeneralMultivector<f64, Const<3>> = GeneralMultivector::scalar(1.0)
+            + GeneralMultivector::from(ScaledBasisBladeOf::new(2.0, 0b011.into()))
+            + GeneralMultivector::from(ScaledBasisBladeOf::new(3.0, 0b100.into()));
+        let b: GeneralMultivector<f64, Const<3>> = GeneralMultivector::from(ScaledBasisBladeOf::new(5.0, 0b001.into()))
+            + GeneralMultivector::from(ScaledBasisBladeOf::new(-1.0, 0b110.into()));
+        let sparse_a = SparseMultivector::from(a.clone());
+        let sparse_b = SparseMultivector::from(b.clone());
+
+        assert_eq!(GeneralMultivector::from(&sparse_a * &sparse_b), &a * &b);
+        assert_eq!(GeneralMultivector::from(&sparse_a ^ &sparse_b), &a ^ &b);
+        assert_eq!(GeneralMultivector::from(&sparse_a << &sparse_b), &a << &b);
+        assert_eq!(GeneralMultivector::from(&sparse_a - &sparse_b), &a - &b);
+        assert_eq!(GeneralMultivector::from(sparse_a.reverse()), a.reverse());
+        assert!((&sparse_b - &sparse_b).is_zero());
+    }
+
+    #[test]
+    fn sparse_multivector_versor_inverse() {
+        let versor: SparseMultivector<f64, Const<3>> = SparseMultivector::scalar(1.0)
+            + SparseMultivector::from(ScaledBasisBladeOf::new(2.0, 0b011.into()));
+        let inverse = versor.versor_inverse().unwrap();
+
+        assert_eq!(versor.norm_squared(), 5.0);
+        assert_eq!(&versor * &inverse, SparseMultivector::scalar(1.0));
+        let zero_divisor: SparseMultivector<f64, Const<3>> = SparseMultivector::scalar(1.0)
+            + SparseMultivector::from(UnitBasisBladeOf::from(0b001u8));
+        assert!(zero_divisor.versor_inverse().is_none());
+    }
+
+    #[test]
+    fn sparse_multivector_metric_product() {
+        type Projective = Signature<f64, U3, U0, U1>;
+
+        let e3: SparseMultivector<f64, Const<4>> = UnitBasisBladeOf::from(0b1000u8).into();
+        let e0: SparseMultivector<f64, Const<4>> = UnitBasisBladeOf::from(0b0001u8).into();
+
+        assert!(e3.product_with(&e3, Projective::geom_blades).is_zero());
+        assert_eq!(e0.product_with(&e0, Projective::geom_blades), SparseMultivector::scalar(1.0));
+    }
+
+    #[test]
+    fn sparse_multivector_wide_bitsets() {
+        type Minkowski = Signature<f64, U199, U1, U0>;
+
+        let e0: SparseMultivector<f64, Const<200>> = UnitBasisBladeOf::basis_vector(0).into();
+        let e199: SparseMultivector<f64, Const<200>> = UnitBasisBladeOf::basis_vector(199).into();
+        let e0_199 = &e0 ^ &e199;
+
+        assert_eq!(e0_199.terms()[0].0.grade(), 2);
+        assert_eq!(e199.product_with(&e199, Minkowski::geom_blades), SparseMultivector::scalar(-1.0));
+        assert_eq!(&e199 * &e0, -e0_199.clone());
+        assert_eq!(e0_199.versor_inverse().unwrap(), -e0_199);
+
+        let blade: SparseMultivector<f64, Const<100>> = UnitBasisBladeOf::from(1u128 << 99 | 1).into();
+        assert_eq!(blade.terms()[0].0.bitset(), 1u128 << 99 | 1);
+    }
+}
@@ -0,0 +1,21 @@
+//! Helpers shared by the tests of multiple modules
+
+use dimension::Dimension;
+use unit_basis_blade::UnitBasisBladeOf;
+use general_multivector::GeneralMultivector;
+
+/// The vector with the given coordinates in the basis `e1`, `e2`, ...
+pub fn vector<D: Dimension>(coordinates: &[f64]) -> GeneralMultivector<f64, D> {
+    let mut result = GeneralMultivector::zero();
+
+    for (index, &coordinate) in coordinates.iter().enumerate() {
+        result.set_coefficient(&UnitBasisBladeOf::basis_vector(index as u8), coordinate);
+    }
+
+    result
+}
+
+/// Asserts that the norm of the difference of the multivectors is negligible
+pub fn assert_approx_eq<D: Dimension>(a: &GeneralMultivector<f64, D>, b: &GeneralMultivector<f64, D>) {
+    assert!((a - b).norm() < 1e-9, "{:?} != {:?}", a.coefficients(), b.coefficients());
+}
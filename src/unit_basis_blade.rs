@@ -1,127 +1,308 @@
-use std::ops::{BitAnd, BitOr, BitXor};
+use std::ops::{BitAnd, BitOr, BitXor, Mul, Range};
 use std::marker::PhantomData;
-use generic_array::GenericArray;
-use dimension::{CountBits, Dimension, DimensionBitset, BitsetMask, Grade};
-use typenum::*;
+use std::cmp::{self, Eq, Ord, PartialEq, PartialOrd, Ordering};
+use std::hash::{Hash, Hasher};
+use dimension::{Bitset, Const, CountBits, Dim, Dimension, DimensionBitset, Grade};
+use ops::{GeometricProduct, Reverse, Dual, Undual, RightComplement, LeftComplement};
 
-#[derive(Clone, Eq, PartialEq, Debug, Default)]
-pub struct UnitBasisBlade<D: Dimension> {
-    bitset: DimensionBitset,
+/// A basis blade of the orthogonal basis of a space of the dimension `D`.
+/// Generic code is written against this type, concrete blades usually use the alias
+/// `UnitBasisBlade<N>`.
+#[derive(Clone, Debug, Default)]
+pub struct UnitBasisBladeOf<D: Dimension> {
+    bitset: DimensionBitset<D>,
     _marker: PhantomData<D>,
 }
 
+/// A basis blade of a space with `D` basis vectors, `D` may be at most 255
+pub type UnitBasisBlade<const D: usize> = UnitBasisBladeOf<Const<D>>;
+
+/// A basis blade of a space whose dimension is the `typenum` unsigned integer `N`,
+/// kept for the transition to `UnitBasisBlade<N>`
+pub type TypenumUnitBasisBlade<N> = UnitBasisBladeOf<Dim<N>>;
+
 macro_rules! impl_unit_basis_blade_from {
     ($name:ident, $type:ty) => {
         pub fn $name(other: $type) -> Self {
             Self {
-                bitset: (other as DimensionBitset) & <D as BitsetMask<DimensionBitset>>::bitset_mask(),
+                bitset: DimensionBitset::<D>::from_u64(other as u64) & D::bitset_mask(),
                 _marker: PhantomData,
             }
         }
     }
 }
 
-macro_rules! impl_unit_basis_blade_from_integer {
-    ($type:ty) => {
-        impl<D: Dimension> From<$type> for UnitBasisBlade<D> {
-            fn from(other: $type) -> Self {
-                Self {
-                    bitset: (other as DimensionBitset) & <D as BitsetMask<DimensionBitset>>::bitset_mask(),
-                    _marker: PhantomData,
-                }
-            }
-        }
-    }
-}
-
-impl<D: Dimension> UnitBasisBlade<D> {
+impl<D: Dimension> UnitBasisBladeOf<D> {
     pub fn zero() -> Self {
-        UnitBasisBlade {
-            bitset: 0,
+        UnitBasisBladeOf {
+            bitset: Bitset::zero(),
             _marker: PhantomData,
         }
     }
 
-    pub fn new(bitset: DimensionBitset) -> Self {
-        UnitBasisBlade {
+    pub fn new(bitset: DimensionBitset<D>) -> Self {
+        UnitBasisBladeOf {
             bitset,
             _marker: PhantomData,
         }
     }
 
+    /// The basis blade containing only the basis vector with the given index,
+    /// indices start from 0
+    pub fn basis_vector(index: u8) -> Self {
+        Self::new(DimensionBitset::<D>::basis_vector(index))
+    }
+
     /// Indices start from 0
     #[inline]
     pub fn contains_basis_vector(&self, index: u8) -> bool {
-        ((1 << index) & self.bitset) != 0
+        (DimensionBitset::<D>::basis_vector(index) & self.bitset) != Bitset::zero()
     }
 
-    pub fn basis_vectors(&self) -> GenericArray<bool, D> {
-        GenericArray::generate(|index| self.contains_basis_vector(index as u8))
+    /// The unit pseudoscalar `I`, containing all basis vectors
+    pub fn pseudoscalar() -> Self {
+        Self::new(D::bitset_mask())
     }
 
-    pub fn bitset(&self) -> DimensionBitset {
+    /// The basis blade containing exactly the basis vectors not contained in `self`
+    pub fn complement(&self) -> Self {
+        Self::new(!self.bitset & D::bitset_mask())
+    }
+
+    pub fn bitset(&self) -> DimensionBitset<D> {
         self.bitset
     }
 
     pub fn is_zero(&self) -> bool {
-        self.bitset == 0
+        self.bitset == Bitset::zero()
+    }
+
+    /// Returns `true` if an odd number of swaps of basis vectors is required to bring
+    /// the concatenation of `self` and `rhs` into canonical order
+    pub fn canonical_reordering_is_odd(&self, rhs: &Self) -> bool {
+        self.bitset.canonical_reordering_is_odd(rhs.bitset)
+    }
+
+    /// The position of the blade in the graded order of all `2^D` basis blades, which orders
+    /// the blades by grade and then lexicographically by the indices of their basis vectors,
+    /// e.g. `1, e1, e2, e3, e12, e13, e23, e123` in 3 dimensions.
+    /// Panics if the index does not fit in a `usize`.
+    pub fn to_graded_index(&self) -> usize {
+        self.graded_index().expect("The graded index does not fit in a usize.")
+    }
+
+    /// The blade at the position `index` in the graded order, see `to_graded_index`.
+    /// `None` if the index is not less than `2^D`.
+    pub fn from_graded_index(index: usize) -> Option<Self> {
+        let dimension = D::to_u8();
+        let mut rank = index as u128;
+        let mut grade = 0;
+
+        loop {
+            let count = binomial(dimension, grade)?;
+
+            if rank < count {
+                break;
+            }
+
+            rank -= count;
+            grade += 1;
+
+            if grade > dimension {
+                return None;
+            }
+        }
+
+        // Unrank the mirrored indices `D - 1 - i` in the combinatorial number system,
+        // from the largest to the smallest
+        let mut mirrored_rank = binomial(dimension, grade)? - 1 - rank;
+        let mut bitset = DimensionBitset::<D>::zero();
+        let mut bound = dimension;
+
+        for remaining in (1..grade + 1).rev() {
+            let mut mirrored_index = bound - 1;
+
+            while binomial(mirrored_index, remaining)? > mirrored_rank {
+                mirrored_index -= 1;
+            }
+
+            mirrored_rank -= binomial(mirrored_index, remaining)?;
+            bitset = bitset | Bitset::basis_vector(dimension - 1 - mirrored_index);
+            bound = mirrored_index;
+        }
+
+        Some(Self::new(bitset))
+    }
+
+    /// Iterates over all `2^D` basis blades in the graded order, see `to_graded_index`.
+    /// Panics if the number of blades does not fit in a `usize`.
+    pub fn all() -> GradedBlades<D> {
+        let count = 1usize.checked_shl(D::to_u8() as u32).expect("The number of basis blades does not fit in a usize.");
+
+        GradedBlades::new(0..count)
+    }
+
+    /// Iterates over the basis blades of grade `k` in the graded order, see `to_graded_index`
+    pub fn of_grade(k: u8) -> GradedBlades<D> {
+        let dimension = D::to_u8();
+        let range = (|| {
+            let offset = (0..k).fold(Some(0), |offset, grade| Some(offset? + binomial(dimension, grade)?))?;
+            let count = binomial(dimension, k)?;
+
+            Some(to_usize(offset)?..to_usize(offset + count)?)
+        })();
+
+        GradedBlades::new(range.expect("The graded index does not fit in a usize."))
+    }
+
+    fn graded_index(&self) -> Option<usize> {
+        let dimension = D::to_u8();
+        let grade = self.grade();
+        let offset = (0..grade).fold(Some(0), |offset, k| Some(offset? + binomial(dimension, k)?))?;
+
+        // The lexicographic rank is complementary to the rank of the mirrored indices
+        // `D - 1 - i` in the combinatorial number system
+        let mut mirrored_rank = 0;
+        let mut remaining = grade;
+
+        for index in 0..dimension {
+            if self.contains_basis_vector(index) {
+                mirrored_rank += binomial(dimension - 1 - index, remaining)?;
+                remaining -= 1;
+            }
+        }
+
+        to_usize(offset + binomial(dimension, grade)? - 1 - mirrored_rank)
     }
 }
 
-#[cfg(any(feature = "max-dimension-8", feature = "max-dimension-16", feature = "max-dimension-32", feature = "max-dimension-64"))]
-impl_unit_basis_blade_from_integer!(u8);
+/// The binomial coefficient `n` choose `k`, `None` on overflow
+fn binomial(n: u8, k: u8) -> Option<u128> {
+    if k > n {
+        return Some(0);
+    }
 
-#[cfg(any(feature = "max-dimension-16", feature = "max-dimension-32", feature = "max-dimension-64"))]
-impl_unit_basis_blade_from_integer!(u16);
+    (0..cmp::min(k, n - k) as u128).fold(Some(1), |result, i| {
+        Some(result?.checked_mul(n as u128 - i)? / (i + 1))
+    })
+}
 
-#[cfg(any(feature = "max-dimension-32", feature = "max-dimension-64"))]
-impl_unit_basis_blade_from_integer!(u32);
+fn to_usize(value: u128) -> Option<usize> {
+    if value <= usize::max_value() as u128 { Some(value as usize) } else { None }
+}
 
-#[cfg(feature = "max-dimension-64")]
-impl_unit_basis_blade_from_integer!(u64);
+/// An iterator over basis blades in the graded order, see `UnitBasisBlade::to_graded_index`
+#[derive(Clone, Debug)]
+pub struct GradedBlades<D: Dimension> {
+    indices: Range<usize>,
+    _marker: PhantomData<D>,
+}
 
-impl<D: Dimension> From<GenericArray<bool, D>> for UnitBasisBlade<D> {
-    fn from(other: GenericArray<bool, D>) -> Self {
-        UnitBasisBlade {
-            bitset: other.iter().enumerate().fold(0, |folded, (index, item)| {
-                folded | if *item { 1 << index } else { 0 }
-            }),
+impl<D: Dimension> GradedBlades<D> {
+    fn new(indices: Range<usize>) -> Self {
+        GradedBlades {
+            indices,
             _marker: PhantomData,
         }
     }
 }
 
-macro_rules! impl_unit_basis_blade_from_array {
-    ($dimension_ty:ty, $dimension_expr:expr) => {
-        impl From<[bool; $dimension_expr]> for UnitBasisBlade<$dimension_ty> {
-            fn from(other: [bool; $dimension_expr]) -> Self {
-                GenericArray::<bool, $dimension_ty>::from(other).into()
-            }
+impl<D: Dimension> Iterator for GradedBlades<D> {
+    type Item = UnitBasisBladeOf<D>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.indices.next().and_then(UnitBasisBladeOf::from_graded_index)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.indices.size_hint()
+    }
+}
+
+impl<D: Dimension> ExactSizeIterator for GradedBlades<D> {}
+
+impl<D: Dimension> PartialEq for UnitBasisBladeOf<D> {
+    fn eq(&self, other: &Self) -> bool {
+        self.bitset == other.bitset
+    }
+}
+
+impl<D: Dimension> Eq for UnitBasisBladeOf<D> {}
+
+impl<D: Dimension> PartialOrd for UnitBasisBladeOf<D> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<D: Dimension> Ord for UnitBasisBladeOf<D> {
+    /// Orders the blades by grade, then lexicographically by the indices of their basis vectors,
+    /// consistently with `to_graded_index`
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.grade().cmp(&other.grade()).then_with(|| {
+            // The blade containing the lowest basis vector contained in only one of the blades is smaller
+            let difference = self.bitset ^ other.bitset;
+
+            (0..D::to_u8())
+                .find(|&index| difference & Bitset::basis_vector(index) != Bitset::zero())
+                .map(|index| if self.contains_basis_vector(index) { Ordering::Less } else { Ordering::Greater })
+                .unwrap_or(Ordering::Equal)
+        })
+    }
+}
+
+impl<D: Dimension> Hash for UnitBasisBladeOf<D> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.bitset.hash(state);
+    }
+}
+
+// Integer literals default to `u8` conversions for every dimension,
+// wider integers convert only to the dimensions stored in a bitset of the same width
+impl<D: Dimension> From<u8> for UnitBasisBladeOf<D> {
+    fn from(other: u8) -> Self {
+        Self {
+            bitset: DimensionBitset::<D>::from_u64(other as u64) & D::bitset_mask(),
+            _marker: PhantomData,
         }
     }
 }
 
-macro_rules! impl_unit_basis_blade_from_array_multiple {
-    ($($dimension_ty:ty, $dimension_expr:expr);+) => {
+macro_rules! impl_unit_basis_blade_from_integer {
+    ($($type:ty),+) => {
         $(
-            impl_unit_basis_blade_from_array!($dimension_ty, $dimension_expr);
+            impl<D: Dimension<Bitset=$type>> From<$type> for UnitBasisBladeOf<D> {
+                fn from(other: $type) -> Self {
+                    Self::new(other & D::bitset_mask())
+                }
+            }
         )+
     }
 }
 
-#[cfg(any(feature = "max-dimension-8", feature = "max-dimension-16", feature = "max-dimension-32", feature = "max-dimension-64"))]
-impl_unit_basis_blade_from_array_multiple!(U1, 1; U2, 2; U3, 3; U4, 4; U5, 5; U6, 6; U7, 7; U8, 8);
+impl_unit_basis_blade_from_integer!(u16, u32, u64, u128);
 
-#[cfg(any(feature = "max-dimension-16", feature = "max-dimension-32", feature = "max-dimension-64"))]
-impl_unit_basis_blade_from_array_multiple!(U9, 9; U10, 10; U11, 11; U12, 12; U13, 13; U14, 14; U15, 15; U16, 16);
+impl<const D: usize> UnitBasisBladeOf<Const<D>> where Const<D>: Dimension {
+    pub fn basis_vectors(&self) -> [bool; D] {
+        let mut result = [false; D];
 
-#[cfg(any(feature = "max-dimension-32", feature = "max-dimension-64"))]
-impl_unit_basis_blade_from_array_multiple!(U17, 17; U18, 18; U19, 19; U20, 20; U21, 21; U22, 22; U23, 23; U24, 24; U25, 25; U26, 26; U27, 27; U28, 28; U29, 29; U30, 30; U31, 31; U32, 32);
+        for (index, item) in result.iter_mut().enumerate() {
+            *item = self.contains_basis_vector(index as u8);
+        }
 
-#[cfg(feature = "max-dimension-64")]
-impl_unit_basis_blade_from_array_multiple!(U33, 33; U34, 34; U35, 35; U36, 36; U37, 37; U38, 38; U39, 39; U40, 40; U41, 41; U42, 42; U43, 43; U44, 44; U45, 45; U46, 46; U47, 47; U48, 48; U49, 49; U50, 50; U51, 51; U52, 52; U53, 53; U54, 54; U55, 55; U56, 56; U57, 57; U58, 58; U59, 59; U60, 60; U61, 61; U62, 62; U63, 63; U64, 64);
+        result
+    }
+}
 
-impl<D: Dimension> Grade for UnitBasisBlade<D> {
+impl<const D: usize> From<[bool; D]> for UnitBasisBladeOf<Const<D>> where Const<D>: Dimension {
+    fn from(other: [bool; D]) -> Self {
+        other.iter().enumerate().fold(Self::zero(), |folded, (index, item)| {
+            if *item { folded | Self::basis_vector(index as u8) } else { folded }
+        })
+    }
+}
+
+impl<D: Dimension> Grade for UnitBasisBladeOf<D> {
     fn grade(&self) -> u8 {
         self.bitset.count_bits()
     }
@@ -132,9 +313,9 @@ impl_operator! {
     inline: [true];
     operator_fn: bitand;
     generics: [D: Dimension];
-    header: (UnitBasisBlade<D>, UnitBasisBlade<D>) -> UnitBasisBlade<D>;
+    header: (UnitBasisBladeOf<D>, UnitBasisBladeOf<D>) -> UnitBasisBladeOf<D>;
     |&lhs, &rhs| {
-        UnitBasisBlade::new(BitAnd::bitand(lhs.bitset, rhs.bitset))
+        UnitBasisBladeOf::new(BitAnd::bitand(lhs.bitset, rhs.bitset))
     }
 }
 
@@ -143,9 +324,9 @@ impl_operator! {
     inline: [true];
     operator_fn: bitor;
     generics: [D: Dimension];
-    header: (UnitBasisBlade<D>, UnitBasisBlade<D>) -> UnitBasisBlade<D>;
+    header: (UnitBasisBladeOf<D>, UnitBasisBladeOf<D>) -> UnitBasisBladeOf<D>;
     |&lhs, &rhs| {
-        UnitBasisBlade::new(BitOr::bitor(lhs.bitset, rhs.bitset))
+        UnitBasisBladeOf::new(BitOr::bitor(lhs.bitset, rhs.bitset))
     }
 }
 
@@ -154,31 +335,236 @@ impl_operator! {
     inline: [true];
     operator_fn: bitxor;
     generics: [D: Dimension];
-    header: (UnitBasisBlade<D>, UnitBasisBlade<D>) -> UnitBasisBlade<D>;
+    header: (UnitBasisBladeOf<D>, UnitBasisBladeOf<D>) -> UnitBasisBladeOf<D>;
     |&lhs, &rhs| {
-        UnitBasisBlade::new(BitXor::bitxor(lhs.bitset, rhs.bitset))
+        UnitBasisBladeOf::new(BitXor::bitxor(lhs.bitset, rhs.bitset))
+    }
+}
+
+impl_operator_geom! {
+    inline: [true];
+    generics: [D: Dimension];
+    header: (UnitBasisBladeOf<D>, UnitBasisBladeOf<D>) -> (i8, UnitBasisBladeOf<D>);
+    |&lhs, &rhs| {
+        // Under the Euclidean metric, every basis vector squares to 1
+        let sign = if lhs.canonical_reordering_is_odd(rhs) { -1 } else { 1 };
+
+        (sign, lhs ^ rhs)
+    }
+}
+
+// A sign of 0 denotes a vanishing product
+impl_operators_inner! {
+    inline: [true];
+    generics: [D: Dimension];
+    header: (UnitBasisBladeOf<D>, UnitBasisBladeOf<D>) -> (i8, UnitBasisBladeOf<D>);
+    |&lhs, &rhs, selection| {
+        let (sign, product) = GeometricProduct::geom(lhs, rhs);
+
+        if selection(lhs.grade(), rhs.grade()) == Some(product.grade()) {
+            (sign, product)
+        } else {
+            (0, UnitBasisBladeOf::zero())
+        }
+    }
+}
+
+impl_unary_operators_involution! {
+    inline: [true];
+    generics: [D: Dimension];
+    header: (UnitBasisBladeOf<D>) -> (i8, UnitBasisBladeOf<D>);
+    |&this, negates| {
+        (if negates(this.grade()) { -1 } else { 1 }, this.clone())
+    }
+}
+
+// Under the Euclidean metric, `I^-1 = ~I`
+impl_unary_operator! {
+    operator_type: [Dual];
+    inline: [true];
+    operator_fn: dual;
+    generics: [D: Dimension];
+    header: (UnitBasisBladeOf<D>) -> (i8, UnitBasisBladeOf<D>);
+    |&this| {
+        let (reverse_sign, inverse_pseudoscalar) = UnitBasisBladeOf::pseudoscalar().reverse();
+        let (sign, product) = GeometricProduct::geom(this, &inverse_pseudoscalar);
+
+        (sign * reverse_sign, product)
+    }
+}
+
+impl_unary_operator! {
+    operator_type: [Undual];
+    inline: [true];
+    operator_fn: undual;
+    generics: [D: Dimension];
+    header: (UnitBasisBladeOf<D>) -> (i8, UnitBasisBladeOf<D>);
+    |&this| {
+        GeometricProduct::geom(this, &UnitBasisBladeOf::pseudoscalar())
+    }
+}
+
+impl_unary_operator! {
+    operator_type: [RightComplement];
+    inline: [true];
+    operator_fn: right_complement;
+    generics: [D: Dimension];
+    header: (UnitBasisBladeOf<D>) -> (i8, UnitBasisBladeOf<D>);
+    |&this| {
+        let complement = this.complement();
+
+        (if this.canonical_reordering_is_odd(&complement) { -1 } else { 1 }, complement)
+    }
+}
+
+impl_unary_operator! {
+    operator_type: [LeftComplement];
+    inline: [true];
+    operator_fn: left_complement;
+    generics: [D: Dimension];
+    header: (UnitBasisBladeOf<D>) -> (i8, UnitBasisBladeOf<D>);
+    |&this| {
+        let complement = this.complement();
+
+        (if complement.canonical_reordering_is_odd(this) { -1 } else { 1 }, complement)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use typenum::U3;
+    use ops::{GradeInvolution, CliffordConjugate};
+    use typenum::U12;
 
     #[test]
     fn unit_basis_blade_from_bool_array() {
-        let blade: UnitBasisBlade<_> = [false, true, true].into();
+        let blade: UnitBasisBladeOf<_> = [false, true, true].into();
 
         assert_eq!(blade.bitset, 6);
-        assert_eq!(blade.basis_vectors(), [false, true, true].into());
+        assert_eq!(blade.basis_vectors(), [false, true, true]);
     }
 
     #[test]
     fn unit_basis_blade_from_u8() {
-        let blade = UnitBasisBlade::<U3>::from(0xFF);
+        let blade = UnitBasisBlade::<3>::from(0xFF);
 
         // Check that a bitmap was applied
         assert_eq!(blade.bitset, 7);
-        assert_eq!(blade.basis_vectors(), [true, true, true].into());
+        assert_eq!(blade.basis_vectors(), [true, true, true]);
+    }
+
+    #[test]
+    fn typenum_transition_aliases() {
+        let blade: TypenumUnitBasisBlade<U12> = 0xF80Fu16.into();
+
+        assert_eq!(blade, UnitBasisBlade::<12>::from(0x000Fu8) | UnitBasisBlade::<12>::basis_vector(11));
+        assert_eq!(blade.basis_vectors().len(), 12);
+    }
+
+    #[test]
+    fn geometric_product() {
+        let e1 = UnitBasisBlade::<3>::from(0b001u8);
+        let e2 = UnitBasisBlade::<3>::from(0b010u8);
+
+        assert_eq!(&e1 * &e2, (1, 0b011.into()));
+        assert_eq!(&e2 * &e1, (-1, 0b011.into()));
+        assert_eq!(&e1 * &e1, (1, UnitBasisBladeOf::zero()));
+    }
+
+    #[test]
+    fn contractions() {
+        let e1 = UnitBasisBlade::<3>::from(0b001u8);
+        let e12 = UnitBasisBlade::<3>::from(0b011u8);
+
+        assert_eq!(&e1 << &e12, (1, 0b010.into()));
+        assert_eq!(&e12 << &e1, (0, UnitBasisBladeOf::zero()));
+        assert_eq!(&e12 >> &e1, (-1, 0b010.into()));
+    }
+
+    #[test]
+    fn involutions() {
+        let e1 = UnitBasisBlade::<3>::from(0b001u8);
+        let e12 = UnitBasisBlade::<3>::from(0b011u8);
+        let e123 = UnitBasisBlade::<3>::from(0b111u8);
+
+        assert_eq!(e1.clone().reverse(), (1, e1.clone()));
+        assert_eq!(e12.clone().reverse(), (-1, e12.clone()));
+        assert_eq!(e123.clone().reverse(), (-1, e123.clone()));
+        assert_eq!(e1.clone().grade_involution(), (-1, e1.clone()));
+        assert_eq!(e12.clone().grade_involution(), (1, e12.clone()));
+        assert_eq!(e1.clifford_conjugate(), (-1, 0b001.into()));
+        assert_eq!(e12.clifford_conjugate(), (-1, 0b011.into()));
+        assert_eq!(e123.clifford_conjugate(), (1, 0b111.into()));
+    }
+
+    #[test]
+    fn dual_and_complements() {
+        let e1 = UnitBasisBlade::<3>::from(0b001u8);
+        let e2 = UnitBasisBlade::<3>::from(0b010u8);
+
+        // e1 I^-1 = e1 e321 = -e23
+        assert_eq!(e1.clone().dual(), (-1, 0b110.into()));
+        assert_eq!(e1.clone().undual(), (1, 0b110.into()));
+        assert_eq!(e2.clone().right_complement(), (-1, 0b101.into()));
+        assert_eq!(e2.clone().left_complement(), (-1, 0b101.into()));
+
+        // e12 ^ e3 = I, but e3 ^ e1 = -e13
+        assert_eq!(UnitBasisBlade::<3>::from(0b011u8).right_complement(), (1, 0b100.into()));
+        assert_eq!(e1.left_complement(), (1, 0b110.into()));
+    }
+
+    #[test]
+    fn mixed_dimensions() {
+        let small = UnitBasisBlade::<3>::pseudoscalar();
+        let large = UnitBasisBlade::<40>::pseudoscalar();
+        let full = UnitBasisBlade::<64>::pseudoscalar();
+
+        assert_eq!(small.bitset(), 0b111u8);
+        assert_eq!(large.bitset(), (1u64 << 40) - 1);
+        assert_eq!(full.bitset(), !0u64);
+        assert_eq!(full.grade(), 64);
+        assert!(full.complement().is_zero());
+
+        let e40 = UnitBasisBlade::<64>::basis_vector(39);
+        let e64 = UnitBasisBlade::<64>::basis_vector(63);
+
+        assert!(e64.contains_basis_vector(63));
+        assert_eq!(&e64 * &e40, (-1, UnitBasisBladeOf::from((1u64 << 63) | (1u64 << 39))));
+        assert_eq!(UnitBasisBlade::<40>::from(!0u64), large);
+    }
+
+    #[test]
+    fn graded_order() {
+        let expected: Vec<u8> = vec![0b0000, 0b0001, 0b0010, 0b0100, 0b1000, 0b0011, 0b0101, 0b1001,
+                                     0b0110, 0b1010, 0b1100, 0b0111, 0b1011, 0b1101, 0b1110, 0b1111];
+        let all: Vec<UnitBasisBlade<4>> = UnitBasisBladeOf::all().collect();
+
+        assert_eq!(all.iter().map(|blade| blade.bitset()).collect::<Vec<_>>(), expected);
+        assert_eq!(UnitBasisBlade::<4>::of_grade(2).len(), 6);
+        assert_eq!(UnitBasisBlade::<4>::of_grade(2).collect::<Vec<_>>(), all[5..11].to_vec());
+        assert_eq!(UnitBasisBlade::<4>::of_grade(5).count(), 0);
+
+        for (index, blade) in all.iter().enumerate() {
+            assert_eq!(blade.to_graded_index(), index);
+            assert_eq!(UnitBasisBladeOf::from_graded_index(index).as_ref(), Some(blade));
+        }
+
+        assert_eq!(UnitBasisBlade::<4>::from_graded_index(16), None);
+        assert_eq!(UnitBasisBlade::<40>::pseudoscalar().to_graded_index(), (1 << 40) - 1);
+        assert_eq!(UnitBasisBlade::<100>::from_graded_index(2).map(|blade| blade.bitset()), Some(0b10u128));
+    }
+
+    #[test]
+    fn ord_matches_graded_order() {
+        use std::collections::HashSet;
+
+        let mut blades: Vec<UnitBasisBlade<5>> = UnitBasisBladeOf::all().collect();
+        blades.reverse();
+        blades.sort();
+
+        assert!(blades.iter().enumerate().all(|(index, blade)| blade.to_graded_index() == index));
+        assert!(UnitBasisBlade::<5>::from(0b00110u8) < UnitBasisBladeOf::from(0b11000u8));
+        assert!(UnitBasisBlade::<5>::from(0b01001u8) < UnitBasisBladeOf::from(0b00110u8));
+        assert_eq!(blades.iter().cloned().collect::<HashSet<_>>().len(), 32);
     }
 }